@@ -0,0 +1,81 @@
+//! Compares two runs of an Intcode program.
+//!
+//! Usage:
+//! - `intcode_diff [--limit N] PROGRAM LEFT_INPUT RIGHT_INPUT` runs `PROGRAM`
+//!   once with each comma-separated input list, for at most `N` instructions
+//!   each (`DEFAULT_LIMIT` if not given), and reports where the runs diverged.
+//! - `intcode_diff --states LEFT RIGHT` compares two saved machine states,
+//!   without running them, so it does not take `--limit`.
+//!
+//! Exits with 0 if there are no differences, 1 if there are, 2 on errors and
+//! 3 if either run reached the instruction limit.
+
+use aoc2019_2::diff::{DEFAULT_LIMIT, compare, diff_runs};
+use aoc2019_2::{Model, state};
+use std::process::exit;
+
+/// Parses a comma-separated list of input values.
+fn parse_input(s: &str) -> Result<Vec<i64>, String> {
+  s.split(',')
+    .filter(|v| !v.trim().is_empty())
+    .map(|v| v.trim().parse().map_err(|_| format!("Bad input value: {v}")))
+    .collect()
+}
+
+const USAGE: &str = "Usage: intcode_diff [--limit N] PROGRAM LEFT_INPUT \
+                     RIGHT_INPUT\n       intcode_diff --states LEFT RIGHT";
+
+/// Runs the comparison requested on the command line and returns the exit
+/// code.
+fn run(args: &[String]) -> Result<i32, String> {
+  let (limit, args) = match args {
+    [flag, limit, rest @ ..] if flag == "--limit" =>
+      (Some(limit.parse().map_err(|_| format!("Bad limit: {limit}"))?), rest),
+    _ => (None, args),
+  };
+  match args {
+    [flag, ..] if flag == "--states" && limit.is_some() =>
+      Err(format!("--states does not take --limit\n{USAGE}")),
+    [flag, left, right] if flag == "--states" => {
+      let load =
+        |path: &str| state::load(path).map_err(|e| format!("{path}: {e}"));
+      let (left, right) = (load(left)?, load(right)?);
+      let memory = compare(&left, &right);
+      println!("{} address(es) differ", memory.len());
+      for d in &memory {
+        println!("  [{}] left {} right {}", d.address, d.left, d.right);
+      }
+      Ok(i32::from(!memory.is_empty() || left != right))
+    },
+    [program, left, right] => {
+      let source = std::fs::read_to_string(program)
+        .map_err(|e| format!("{program}: {e}"))?;
+      let program: Model =
+        source.parse().map_err(|e| format!("{program}: {e}"))?;
+      let diff = diff_runs(
+        &program,
+        &parse_input(left)?,
+        &parse_input(right)?,
+        limit.unwrap_or(DEFAULT_LIMIT),
+      );
+      print!("{diff}");
+      Ok(if diff.left.limit_reached || diff.right.limit_reached {
+        3
+      } else {
+        i32::from(diff.divergence.is_some() || !diff.memory.is_empty())
+      })
+    },
+    _ => Err(USAGE.into()),
+  }
+}
+
+fn main() {
+  let args: Vec<String> = std::env::args().skip(1).collect();
+  match run(&args) {
+    Ok(code) => exit(code),
+    Err(e) => {
+      eprintln!("{e}");
+      exit(2);
+    },
+  }
+}
@@ -0,0 +1,262 @@
+//! Terminal visualiser for Intcode programs.
+//!
+//! Usage: `visualiser [PROGRAM] [--delay MS] [--input V1,V2,...]`
+//!
+//! Controls: `space` play/pause, `s` or `→` single step, `+`/`-` change the
+//! delay between steps while playing, `r` reset, `q` or `Esc` quit.
+
+use aoc2019_2::trace;
+use aoc2019_2::{Model, Status, instruction_length};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::{
+  Attribute, Color, Print, SetAttribute, SetForegroundColor,
+};
+use crossterm::{cursor, execute, queue, terminal};
+use std::io::{self, Stdout, Write};
+use std::time::Duration;
+
+/// Number of history lines shown at the bottom of the screen.
+const HISTORY_LINES: u16 = 8;
+
+/// Amount by which `+` and `-` change the delay.
+const DELAY_STEP_MS: u64 = 50;
+
+/// Visualiser state.
+struct App {
+  /// The program as loaded, used when resetting.
+  program: Model,
+  model: Model,
+  operations_history: Vec<String>,
+  playing: bool,
+  visual_delay_ms: u64,
+  /// Address written by the last operation, highlighted in the grid.
+  last_write: Option<usize>,
+}
+
+impl App {
+  fn new(program: Model, visual_delay_ms: u64) -> Self {
+    Self {
+      model: program.clone(),
+      program,
+      operations_history: vec!["Initializing Logic".into()],
+      playing: false,
+      visual_delay_ms,
+      last_write: None,
+    }
+  }
+
+  fn reset(&mut self) {
+    *self = Self::new(self.program.clone(), self.visual_delay_ms);
+  }
+
+  /// Executes one instruction and logs what happened.
+  fn step(&mut self) {
+    if self.model.halted {
+      self.playing = false;
+      return;
+    }
+    match trace::step(&mut self.model) {
+      Ok(operation) => {
+        self.last_write = operation.write.map(|(address, _)| address);
+        if operation.status != Status::Running {
+          self.playing = false;
+        }
+        self.operations_history.push(operation.to_string());
+      },
+      Err(e) => {
+        self.playing = false;
+        self.operations_history.push(format!("Error, {e}"));
+      },
+    }
+  }
+
+  const fn status(&self) -> &'static str {
+    match (self.model.halted, self.playing) {
+      (true, _) => "halted",
+      (false, true) => "playing",
+      (false, false) => "paused",
+    }
+  }
+}
+
+/// Restores the terminal when the visualiser exits, including on errors.
+struct TerminalGuard;
+
+impl TerminalGuard {
+  fn new(stdout: &mut Stdout) -> io::Result<Self> {
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+    Ok(Self)
+  }
+}
+
+impl Drop for TerminalGuard {
+  fn drop(&mut self) {
+    let _ =
+      execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+  }
+}
+
+/// Writes a queue of values as a comma-separated list.
+fn format_queue<'a>(values: impl Iterator<Item = &'a i64>) -> String {
+  values.map(ToString::to_string).collect::<Vec<_>>().join(",")
+}
+
+/// Draws the registers, the memory grid and the operation history.
+fn draw(stdout: &mut Stdout, app: &App) -> io::Result<()> {
+  let (width, height) = terminal::size()?;
+  let m = &app.model;
+  queue!(
+    stdout,
+    terminal::Clear(terminal::ClearType::All),
+    cursor::MoveTo(0, 0)
+  )?;
+
+  // Header and registers.
+  queue!(
+    stdout,
+    Print(format!(
+      "[{}] delay {} ms | space play/pause, s step, +/- delay, r reset, q quit",
+      app.status(),
+      app.visual_delay_ms
+    )),
+    cursor::MoveTo(0, 1),
+    Print(format!(
+      "ip {}  relative base {}  steps {}  input [{}]  output [{}]",
+      m.position,
+      m.relative_base,
+      m.instruction_count,
+      format_queue(m.input.iter()),
+      format_queue(m.output.iter())
+    )),
+  )?;
+
+  // Memory grid, scrolled so that the current instruction is visible.
+  let cell_width =
+    m.int_code.iter().map(|v| v.to_string().len()).max().unwrap_or(1) + 1;
+  let columns = (usize::from(width) / cell_width).max(1);
+  let grid_rows = usize::from(height.saturating_sub(HISTORY_LINES + 4)).max(1);
+  let current_row = m.position / columns;
+  let first_row = current_row.saturating_sub(grid_rows - 1);
+  let current = m.position..m.position + instruction_length(m.get(m.position));
+
+  let rows =
+    m.int_code.chunks(columns).enumerate().skip(first_row).take(grid_rows);
+  for (y, (row, chunk)) in (3..).zip(rows) {
+    queue!(stdout, cursor::MoveTo(0, y))?;
+    for (column, value) in chunk.iter().enumerate() {
+      let address = row * columns + column;
+      if current.contains(&address) {
+        queue!(stdout, SetAttribute(Attribute::Reverse))?;
+      } else if app.last_write == Some(address) {
+        queue!(stdout, SetForegroundColor(Color::Green))?;
+      }
+      queue!(
+        stdout,
+        Print(format!("{:>w$}", value, w = cell_width - 1)),
+        SetAttribute(Attribute::Reset),
+        Print(" ")
+      )?;
+    }
+  }
+
+  // Operation history, newest last.
+  let history_top = height.saturating_sub(HISTORY_LINES);
+  let skip =
+    app.operations_history.len().saturating_sub(usize::from(HISTORY_LINES));
+  for (y, line) in (history_top..).zip(app.operations_history.iter().skip(skip))
+  {
+    let line: String = line.chars().take(usize::from(width)).collect();
+    queue!(stdout, cursor::MoveTo(0, y), Print(line))?;
+  }
+
+  stdout.flush()
+}
+
+/// Parses the command line into a program and a delay.
+fn parse_args() -> Result<(Model, u64), String> {
+  let mut path = String::from("src/input_2a");
+  let mut delay = 200;
+  let mut input = Vec::new();
+
+  let mut args = std::env::args().skip(1);
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--delay" => {
+        let value = args.next().ok_or("--delay needs a value")?;
+        delay = value.parse().map_err(|_| format!("Bad delay: {value}"))?;
+      },
+      "--input" => {
+        let value = args.next().ok_or("--input needs a value")?;
+        for v in value.split(',').filter(|v| !v.is_empty()) {
+          input.push(
+            v.parse::<i64>().map_err(|_| format!("Bad input value: {v}"))?,
+          );
+        }
+      },
+      _ => path = arg,
+    }
+  }
+
+  let source =
+    std::fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+  let mut model: Model = source.parse().map_err(|e| format!("{path}: {e}"))?;
+  model.input.extend(input);
+  Ok((model, delay))
+}
+
+fn main() -> io::Result<()> {
+  let (program, delay) = match parse_args() {
+    Ok(args) => args,
+    Err(e) => {
+      eprintln!("{e}");
+      std::process::exit(2);
+    },
+  };
+  let mut app = App::new(program, delay);
+
+  let mut stdout = io::stdout();
+  let _guard = TerminalGuard::new(&mut stdout)?;
+
+  loop {
+    draw(&mut stdout, &app)?;
+
+    // Wait for a key press, or for the delay to pass while playing.
+    let timeout = Duration::from_millis(if app.playing {
+      app.visual_delay_ms
+    } else {
+      250
+    });
+    if !event::poll(timeout)? {
+      if app.playing {
+        app.step();
+      }
+      continue;
+    }
+
+    if let Event::Key(key) = event::read()? {
+      if key.kind != KeyEventKind::Press {
+        continue;
+      }
+      match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => break,
+        KeyCode::Char(' ') => app.playing = !app.playing && !app.model.halted,
+        KeyCode::Char('s') | KeyCode::Right => {
+          app.playing = false;
+          app.step();
+        },
+        KeyCode::Char('+') =>
+          app.visual_delay_ms =
+            app.visual_delay_ms.saturating_add(DELAY_STEP_MS),
+        KeyCode::Char('-') =>
+          app.visual_delay_ms =
+            app.visual_delay_ms.saturating_sub(DELAY_STEP_MS),
+        KeyCode::Char('r') => app.reset(),
+        _ => (),
+      }
+    }
+  }
+
+  Ok(())
+}
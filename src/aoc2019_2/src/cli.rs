@@ -0,0 +1,276 @@
+use crate::{IntcodeError, MEMORY_LIMIT, Model, Status};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+
+/// Usage text printed for `--help` and after argument errors.
+pub const USAGE: &str = "\
+Usage: aoc2019_2 [PROGRAM] [OPTIONS]
+
+Runs an Intcode program read from PROGRAM, or from stdin if PROGRAM is
+missing or `-`.
+
+Options:
+  --set ADDR=VALUE   Write VALUE to ADDR before running (repeatable)
+  --input V,...      Values for opcode 3, in order (repeatable)
+  --format FORMAT    Output format, `plain` (default) or `json`
+  --limit N          Stop after N instructions (each attempt of --find
+                     stops after 10000 unless a limit is given)
+  --find TARGET      Find the noun and verb, written to addresses 1 and 2,
+                     for which address 0 ends up as TARGET (Problem 2b)
+  --help             Show this message
+
+Exit codes: 0 halted, 1 Intcode error, 2 bad arguments or unreadable
+program, 3 instruction limit reached, 4 waiting for more input. With
+--find: 0 found, 1 not found.";
+
+/// How `Outcome` is printed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutputFormat {
+  /// Outputs one per line, followed by a summary.
+  Plain,
+  /// A single JSON object.
+  Json,
+}
+
+/// Parsed command line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Options {
+  /// Path of the program, `None` to read stdin.
+  pub program: Option<String>,
+  /// `(address, value)` pairs written before running, in order.
+  pub patches: Vec<(usize, i64)>,
+  pub input: Vec<i64>,
+  pub format: OutputFormat,
+  /// Maximum number of instructions to execute.
+  pub limit: Option<u64>,
+  /// Value of address 0 to search for with `find_noun_verb`.
+  pub find: Option<i64>,
+  /// Set by `--help`; nothing is run.
+  pub help: bool,
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Self {
+      program: None,
+      patches: vec![],
+      input: vec![],
+      format: OutputFormat::Plain,
+      limit: None,
+      find: None,
+      help: false,
+    }
+  }
+}
+
+/// Parses an integer argument, naming the flag in the error.
+fn parse_number<T: std::str::FromStr>(
+  flag: &str,
+  s: &str,
+) -> Result<T, String> {
+  s.trim().parse().map_err(|_| format!("{flag}: bad number '{s}'"))
+}
+
+/// Parses the arguments that follow the program name.
+///
+/// # Errors
+///
+/// Returns a message describing the first bad argument.
+pub fn parse_args(args: &[String]) -> Result<Options, String> {
+  let mut options = Options::default();
+  let mut args = args.iter();
+  while let Some(arg) = args.next() {
+    let mut value =
+      |flag: &str| args.next().ok_or_else(|| format!("{flag}: missing value"));
+    match arg.as_str() {
+      "--set" => {
+        let patch = value("--set")?;
+        let (address, v) = patch.split_once('=').ok_or_else(|| {
+          format!("--set: expected ADDR=VALUE, got '{patch}'")
+        })?;
+        let address = parse_number("--set", address)?;
+        if address >= MEMORY_LIMIT {
+          return Err(format!("--set: address {address} is too large"));
+        }
+        options.patches.push((address, parse_number("--set", v)?));
+      },
+      "--input" => {
+        for v in value("--input")?.split(',').filter(|v| !v.trim().is_empty()) {
+          options.input.push(parse_number("--input", v)?);
+        }
+      },
+      "--format" =>
+        options.format = match value("--format")?.as_str() {
+          "plain" => OutputFormat::Plain,
+          "json" => OutputFormat::Json,
+          other => return Err(format!("--format: unknown format '{other}'")),
+        },
+      "--limit" =>
+        options.limit = Some(parse_number("--limit", value("--limit")?)?),
+      "--find" =>
+        options.find = Some(parse_number("--find", value("--find")?)?),
+      "--help" | "-h" => options.help = true,
+      flag if flag.starts_with("--") => {
+        return Err(format!("unknown option '{flag}'"));
+      },
+      path if options.program.is_none() => {
+        options.program = Some(path.to_string()).filter(|p| p != "-");
+      },
+      extra => return Err(format!("unexpected argument '{extra}'")),
+    }
+  }
+  Ok(options)
+}
+
+/// Why a run stopped.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Stop {
+  Halted,
+  AwaitingInput,
+  LimitReached,
+  Error(IntcodeError),
+}
+
+impl Stop {
+  /// Process exit code, as listed in `USAGE`.
+  #[must_use]
+  pub const fn exit_code(&self) -> i32 {
+    match self {
+      Self::Halted => 0,
+      Self::Error(_) => 1,
+      Self::LimitReached => 3,
+      Self::AwaitingInput => 4,
+    }
+  }
+}
+
+impl Display for Stop {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Halted => write!(f, "halted"),
+      Self::AwaitingInput => write!(f, "waiting for input"),
+      Self::LimitReached => write!(f, "instruction limit reached"),
+      Self::Error(e) => write!(f, "error: {e}"),
+    }
+  }
+}
+
+/// The result of `execute`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Outcome {
+  pub model: Model,
+  pub stop: Stop,
+}
+
+/// `Outcome` as written by `--format json`.
+#[derive(Serialize)]
+struct JsonOutcome<'a> {
+  status: &'static str,
+  error: Option<String>,
+  position: usize,
+  instruction_count: u64,
+  output: Vec<i64>,
+  memory: &'a [i64],
+}
+
+impl Outcome {
+  /// The outcome in the requested format, without a trailing newline.
+  ///
+  /// # Panics
+  ///
+  /// Never in practice: serialising the JSON outcome cannot fail.
+  #[must_use]
+  pub fn render(&self, format: OutputFormat) -> String {
+    match format {
+      OutputFormat::Plain => self.to_string(),
+      OutputFormat::Json => {
+        let (status, error) = match &self.stop {
+          Stop::Halted => ("halted", None),
+          Stop::AwaitingInput => ("awaiting_input", None),
+          Stop::LimitReached => ("limit_reached", None),
+          Stop::Error(e) => ("error", Some(e.to_string())),
+        };
+        serde_json::to_string(&JsonOutcome {
+          status,
+          error,
+          position: self.model.position,
+          instruction_count: self.model.instruction_count,
+          output: self.model.output.iter().copied().collect(),
+          memory: &self.model.int_code,
+        })
+        .expect("serialising an outcome cannot fail")
+      },
+    }
+  }
+}
+
+impl Display for Outcome {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    for v in &self.model.output {
+      writeln!(f, "{v}")?;
+    }
+    writeln!(
+      f,
+      "{} after {} instruction(s) at position {}",
+      self.stop, self.model.instruction_count, self.model.position
+    )?;
+    write!(f, "Position 0: {}", self.model.get(0))
+  }
+}
+
+/// Applies the patches and input from `options` to `program` and runs it
+/// until it halts, fails, needs more input or reaches the instruction limit.
+#[must_use]
+pub fn execute(mut program: Model, options: &Options) -> Outcome {
+  for &(address, value) in &options.patches {
+    program.set(address, value);
+  }
+  program.input.extend(&options.input);
+
+  let limit =
+    options.limit.map(|l| program.instruction_count.saturating_add(l));
+  let stop = loop {
+    if program.halted {
+      break Stop::Halted;
+    }
+    if limit.is_some_and(|l| program.instruction_count >= l) {
+      break Stop::LimitReached;
+    }
+    match program.step() {
+      Ok(Status::Running) => (),
+      Ok(Status::Halted) => break Stop::Halted,
+      Ok(Status::AwaitingInput) => break Stop::AwaitingInput,
+      Err(e) => break Stop::Error(e),
+    }
+  };
+  Outcome { model: program, stop }
+}
+
+/// Instructions each attempt of `find_noun_verb` may execute when no
+/// `--limit` is given, so that a noun and verb that make the program loop
+/// forever do not stop the search.
+pub const FIND_LIMIT: u64 = 10_000;
+
+/// Searches for the noun and verb that make a run halt with `target` at
+/// address 0.
+///
+/// Nouns and verbs from 0 to 99 are written to addresses 1 and 2 after the
+/// other patches. Each attempt runs for at most `options.limit` instructions,
+/// or `FIND_LIMIT` if there is none. Returns the first `(noun, verb)` found,
+/// trying nouns in order.
+#[must_use]
+pub fn find_noun_verb(
+  program: &Model,
+  options: &Options,
+  target: i64,
+) -> Option<(i64, i64)> {
+  (0..100).flat_map(|noun| (0..100).map(move |verb| (noun, verb))).find(
+    |&(noun, verb)| {
+      let mut options = options.clone();
+      options.patches.extend([(1, noun), (2, verb)]);
+      options.limit = options.limit.or(Some(FIND_LIMIT));
+      let outcome = execute(program.clone(), &options);
+      outcome.stop == Stop::Halted && outcome.model.get(0) == target
+    },
+  )
+}
@@ -0,0 +1,223 @@
+use crate::trace::{self, Operation};
+use crate::{IntcodeError, Model, Status};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// An address whose value differs between two models.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AddressDiff {
+  pub address: usize,
+  pub left: i64,
+  pub right: i64,
+}
+
+/// Every address whose value differs between two models, in address order.
+/// Memory beyond the end of a program counts as 0, so models of different
+/// lengths can be compared.
+#[must_use]
+pub fn compare(left: &Model, right: &Model) -> Vec<AddressDiff> {
+  let len = left.int_code.len().max(right.int_code.len());
+  (0..len)
+    .map(|address| AddressDiff {
+      address,
+      left: left.get(address),
+      right: right.get(address),
+    })
+    .filter(|d| d.left != d.right)
+    .collect()
+}
+
+/// Instructions each run may execute in `intcode_diff` when no `--limit` is
+/// given.
+pub const DEFAULT_LIMIT: u64 = 1_000_000;
+
+/// Writes to one address, as `(step, value)` pairs.
+pub type Writes = Vec<(u64, i64)>;
+
+/// One of the two runs in a `RunDiff`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Run {
+  /// The model once the run stopped.
+  pub model: Model,
+  /// The error that stopped the run, if it did not halt or wait for input.
+  pub error: Option<IntcodeError>,
+  /// Set if the run was stopped by the instruction limit.
+  pub limit_reached: bool,
+  /// Every write to memory, by address.
+  pub writes: BTreeMap<usize, Writes>,
+}
+
+impl Run {
+  /// Executes one instruction, unless the model has already executed
+  /// `limit` in total. Returns `None` once the run has stopped.
+  fn step(&mut self, limit: u64) -> Option<Operation> {
+    if self.error.is_some() || self.model.halted || self.limit_reached {
+      return None;
+    }
+    if self.model.instruction_count >= limit {
+      self.limit_reached = true;
+      return None;
+    }
+    match trace::step(&mut self.model) {
+      Ok(operation) if operation.status == Status::AwaitingInput => None,
+      Ok(operation) => {
+        if let Some((address, value)) = operation.write {
+          self.writes.entry(address).or_default().push((operation.step, value));
+        }
+        Some(operation)
+      },
+      Err(e) => {
+        self.error = Some(e);
+        None
+      },
+    }
+  }
+}
+
+/// Whether two runs did the same thing, whatever step each of them was at.
+fn same_operation(left: Option<&Operation>, right: Option<&Operation>) -> bool {
+  match (left, right) {
+    (Some(left), Some(right)) =>
+      Operation { step: right.step, ..left.clone() } == *right,
+    (left, right) => left.is_none() && right.is_none(),
+  }
+}
+
+/// The first instruction at which two runs did something different. One
+/// side is `None` if that run had already stopped.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Divergence {
+  pub step: u64,
+  pub left: Option<Operation>,
+  pub right: Option<Operation>,
+}
+
+/// Comparison of two runs of a program.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RunDiff {
+  pub left: Run,
+  pub right: Run,
+  /// `None` if both runs executed exactly the same operations.
+  pub divergence: Option<Divergence>,
+  /// Addresses that differ between the final models.
+  pub memory: Vec<AddressDiff>,
+}
+
+impl RunDiff {
+  /// The writes made by each side to the addresses that ended up different.
+  #[must_use]
+  pub fn watched_writes(&self) -> Vec<(usize, &Writes, &Writes)> {
+    fn writes(run: &Run, address: usize) -> &Writes {
+      const NONE: &Writes = &Vec::new();
+      run.writes.get(&address).unwrap_or(NONE)
+    }
+    self
+      .memory
+      .iter()
+      .map(|d| {
+        (
+          d.address,
+          writes(&self.left, d.address),
+          writes(&self.right, d.address),
+        )
+      })
+      .collect()
+  }
+}
+
+/// Runs `program` once with `left_input` and once with `right_input`, in
+/// lockstep, until both runs halt, need more input, fail or execute `limit`
+/// instructions.
+#[must_use]
+pub fn diff_runs(
+  program: &Model,
+  left_input: &[i64],
+  right_input: &[i64],
+  limit: u64,
+) -> RunDiff {
+  let (mut left, mut right) = (program.clone(), program.clone());
+  left.input.extend(left_input);
+  right.input.extend(right_input);
+  diff_models(left, right, limit)
+}
+
+/// Runs two models in lockstep, until both halt, need more input, fail or
+/// execute `limit` more instructions.
+///
+/// The limit counts like the one in `cli::execute`. Use this when the runs
+/// differ by more than their inputs, such as the noun and verb written
+/// into addresses 1 and 2 for Problem 2.
+///
+/// Operations are compared without their `step`, so models that have already
+/// executed different numbers of instructions, such as two saved states, only
+/// diverge once they do something different.
+#[must_use]
+pub fn diff_models(left: Model, right: Model, limit: u64) -> RunDiff {
+  let left_limit = left.instruction_count.saturating_add(limit);
+  let right_limit = right.instruction_count.saturating_add(limit);
+  let mut left = Run { model: left, ..Run::default() };
+  let mut right = Run { model: right, ..Run::default() };
+  let mut divergence = None;
+
+  loop {
+    let step = left.model.instruction_count.max(right.model.instruction_count);
+    let (l, r) = (left.step(left_limit), right.step(right_limit));
+    if l.is_none() && r.is_none() {
+      break;
+    }
+    if divergence.is_none() && !same_operation(l.as_ref(), r.as_ref()) {
+      divergence = Some(Divergence { step, left: l, right: r });
+    }
+  }
+
+  let memory = compare(&left.model, &right.model);
+  RunDiff { left, right, divergence, memory }
+}
+
+/// Formats the writes of one side as `value@step` entries.
+fn format_writes(writes: &[(u64, i64)]) -> String {
+  if writes.is_empty() {
+    "never written".into()
+  } else {
+    writes
+      .iter()
+      .map(|(step, value)| format!("{value}@{step}"))
+      .collect::<Vec<_>>()
+      .join(", ")
+  }
+}
+
+impl Display for RunDiff {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let describe = |o: &Option<Operation>| {
+      o.as_ref().map_or_else(
+        || "stopped".into(),
+        |o| format!("{} (position {})", o, o.position),
+      )
+    };
+    match &self.divergence {
+      None => writeln!(f, "Runs did not diverge")?,
+      Some(d) => {
+        writeln!(f, "Runs diverged at step {}", d.step)?;
+        writeln!(f, "  left:  {}", describe(&d.left))?;
+        writeln!(f, "  right: {}", describe(&d.right))?;
+      },
+    }
+    for (side, run) in &[("left", &self.left), ("right", &self.right)] {
+      if let Some(e) = &run.error {
+        writeln!(f, "The {side} run failed: {e}")?;
+      }
+      if run.limit_reached {
+        writeln!(f, "The {side} run reached the instruction limit")?;
+      }
+    }
+
+    writeln!(f, "{} address(es) differ", self.memory.len())?;
+    for (d, (_, left, right)) in self.memory.iter().zip(self.watched_writes()) {
+      writeln!(f, "  [{}] left {} right {}", d.address, d.left, d.right)?;
+      writeln!(f, "    left writes:  {}", format_writes(left))?;
+      writeln!(f, "    right writes: {}", format_writes(right))?;
+    }
+    Ok(())
+  }
+}
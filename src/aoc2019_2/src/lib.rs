@@ -1,16 +1,328 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Command line runner used by `main.rs`.
+///
+/// `parse_args` turns the arguments into `Options`, and `execute` runs a
+/// program with them. The exit codes are part of the interface, so scripts can
+/// tell a halted program from one that failed or ran out of input.
+pub mod cli;
+
+/// Comparing two runs of a program.
+///
+/// `compare` lists the addresses that differ between two final models.
+/// `diff_runs` and `diff_models` run two models in lockstep and also report
+/// the first instruction at which the runs diverged and every value written
+/// to the differing addresses on each side.
+pub mod diff;
+
+/// Record-and-replay of a program's I/O.
+///
+/// A `Recorder` wraps a `Model` and logs every input consumed and output
+/// produced, together with the instruction count at which it happened. A
+/// `Recording` can be stored as JSON and later passed to `replay`, which feeds
+/// the recorded inputs back and reports the first step at which the program
+/// does something different. This turns interactive sessions into regression
+/// tests.
+pub mod replay;
+
+/// Saving and loading complete machine states.
+///
+/// A saved state holds everything needed to resume a `Model`: memory,
+/// instruction pointer, relative base, instruction count, both I/O queues and
+/// the halted flag. There are two encodings of the same data:
+///
+/// - JSON, an object with a `version` field, for reading and editing by hand.
+/// - Binary, a compact encoding that starts with `MAGIC` and the version,
+///   followed by the fields as LEB128 variable-length integers. Signed values
+///   are zigzag-encoded so that small negative numbers stay small.
+///
+/// `load` and `from_bytes` recognise either encoding.
+pub mod state;
+
+/// Step-by-step execution with a description of each operation, for
+/// visualisers and debugging output.
+pub mod trace;
+
+/// Browser visualiser, compiled to WASM with `wasm-pack` (see `index.html`).
+///
+/// It shows the memory grid with the current instruction in bold and the
+/// history of operations, stepping the program with `trace::step`.
+#[cfg(feature = "web")]
+pub mod web;
+
+/// Number of memory cells a program can use. Reads beyond it give 0, like any
+/// other memory that was never written, but writes fail, so that a program
+/// cannot grow its memory without bound.
+pub const MEMORY_LIMIT: usize = 1 << 24;
+
+/// Errors that stop an Intcode program before it reaches opcode 99.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IntcodeError {
+  /// The instruction at `position` does not have a known opcode.
+  UnknownOpcode { opcode: i64, position: usize },
+  /// A parameter mode other than 0 (position), 1 (immediate) or 2
+  /// (relative).
+  UnknownMode { mode: i64, position: usize },
+  /// An instruction tried to write to a parameter in immediate mode.
+  ImmediateWrite { position: usize },
+  /// A parameter resolved to an address below zero.
+  NegativeAddress { address: i64, position: usize },
+  /// An instruction tried to write at or beyond `MEMORY_LIMIT`.
+  AddressTooLarge { address: usize, position: usize },
+  /// An addition or multiplication does not fit in an `i64`.
+  Overflow { position: usize },
+  /// `step` was called after the program executed opcode 99.
+  Halted,
+}
+
+impl Display for IntcodeError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::UnknownOpcode { opcode, position } =>
+        write!(f, "unknown opcode {opcode} at position {position}"),
+      Self::UnknownMode { mode, position } =>
+        write!(f, "unknown parameter mode {mode} at position {position}"),
+      Self::ImmediateWrite { position } =>
+        write!(f, "write parameter in immediate mode at position {position}"),
+      Self::NegativeAddress { address, position } =>
+        write!(f, "negative address {address} at position {position}"),
+      Self::AddressTooLarge { address, position } => write!(
+        f,
+        "address {address} is beyond the memory limit at position {position}"
+      ),
+      Self::Overflow { position } =>
+        write!(f, "arithmetic overflow at position {position}"),
+      Self::Halted => write!(f, "program has already halted"),
+    }
+  }
+}
+
+impl std::error::Error for IntcodeError {}
+
+/// A value in a program that is not an `i64`, found by `Model::from_str`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+  /// The address the value would have been loaded at.
+  pub address: usize,
+  /// The value, without the whitespace around it.
+  pub value: String,
+}
+
+impl Display for ParseError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "bad value '{}' at address {}", self.value, self.address)
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The state of the machine after a call to `Model::step` or `Model::run`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+  /// The last instruction completed and the program can keep going.
+  Running,
+  /// The program reached opcode 3 with an empty input queue. Push a value
+  /// onto `Model.input` and step again to continue.
+  AwaitingInput,
+  /// The program executed opcode 99.
+  Halted,
+}
+
 /// Program model.
-#[derive(Default)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Model {
   pub int_code: Vec<i64>,
+  /// Instruction pointer.
+  pub position: usize,
+  /// Base address for parameters in relative mode (mode 2).
+  pub relative_base: i64,
+  /// Values waiting to be read by opcode 3.
+  pub input: VecDeque<i64>,
+  /// Values written by opcode 4, oldest first.
+  pub output: VecDeque<i64>,
+  /// Set once opcode 99 has been executed.
+  pub halted: bool,
+  /// Number of instructions executed so far.
+  pub instruction_count: u64,
 }
 
 impl Model {
-  /// Get a value from the model at the given position.
-  pub fn get(&self, position: usize) -> i64 { self.int_code[position] }
+  /// Get a value from the model at the given position. Memory beyond the end
+  /// of the program reads as 0.
+  #[must_use]
+  pub fn get(&self, position: usize) -> i64 {
+    self.int_code.get(position).copied().unwrap_or(0)
+  }
 
-  /// Set a value on the model.
+  /// Set a value on the model, growing memory with zeroes if required.
+  ///
+  /// This does not check `position` against `MEMORY_LIMIT`, so memory can
+  /// grow as far as the caller asks.
   pub fn set(&mut self, position: usize, value: i64) {
-    self.int_code[position] = value
+    if position >= self.int_code.len() {
+      self.int_code.resize(position + 1, 0);
+    }
+    self.int_code[position] = value;
+  }
+
+  /// The mode of parameter `n` (1-based) of the current instruction.
+  fn mode(&self, n: usize) -> Result<i64, IntcodeError> {
+    // Drop the two opcode digits and the modes of the first n - 1 parameters.
+    let mode = (0..=n).fold(self.get(self.position), |v, _| v / 10) % 10;
+    match mode {
+      0..=2 => Ok(mode),
+      _ => Err(IntcodeError::UnknownMode { mode, position: self.position }),
+    }
+  }
+
+  /// Turns an `i64` into a memory address. Addresses too large for a `usize`
+  /// become `usize::MAX`, which is beyond `MEMORY_LIMIT` anyway.
+  fn address(&self, address: i64) -> Result<usize, IntcodeError> {
+    if address < 0 {
+      Err(IntcodeError::NegativeAddress { address, position: self.position })
+    } else {
+      Ok(usize::try_from(address).unwrap_or(usize::MAX))
+    }
+  }
+
+  /// The address that parameter `n` of the current instruction refers to.
+  pub(crate) fn parameter_address(
+    &self,
+    n: usize,
+  ) -> Result<usize, IntcodeError> {
+    let raw = self.get(self.position + n);
+    match self.mode(n)? {
+      0 => self.address(raw),
+      1 => Err(IntcodeError::ImmediateWrite { position: self.position }),
+      _ => self.address(
+        self
+          .relative_base
+          .checked_add(raw)
+          .ok_or(IntcodeError::Overflow { position: self.position })?,
+      ),
+    }
+  }
+
+  /// Reads the value of parameter `n` of the current instruction.
+  pub(crate) fn read(&self, n: usize) -> Result<i64, IntcodeError> {
+    match self.mode(n)? {
+      1 => Ok(self.get(self.position + n)),
+      _ => Ok(self.get(self.parameter_address(n)?)),
+    }
+  }
+
+  /// Writes `value` to the address in parameter `n` of the current
+  /// instruction.
+  fn write(&mut self, n: usize, value: i64) -> Result<(), IntcodeError> {
+    let address = self.parameter_address(n)?;
+    if address >= MEMORY_LIMIT {
+      return Err(IntcodeError::AddressTooLarge {
+        address,
+        position: self.position,
+      });
+    }
+    self.set(address, value);
+    Ok(())
+  }
+
+  /// Executes the instruction at `position`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an `IntcodeError` if the instruction cannot be executed. The
+  /// model is left unchanged in that case.
+  pub fn step(&mut self) -> Result<Status, IntcodeError> {
+    if self.halted {
+      return Err(IntcodeError::Halted);
+    }
+
+    let opcode = self.get(self.position) % 100;
+    let overflow = IntcodeError::Overflow { position: self.position };
+    match opcode {
+      1 | 2 | 7 | 8 => {
+        let (num1, num2) = (self.read(1)?, self.read(2)?);
+        let result = match opcode {
+          1 => opcode_one(num1, num2).ok_or(overflow)?,
+          2 => opcode_two(num1, num2).ok_or(overflow)?,
+          7 => i64::from(num1 < num2),
+          _ => i64::from(num1 == num2),
+        };
+        self.write(3, result)?;
+        self.position += 4;
+      },
+      3 => match self.input.front() {
+        None => return Ok(Status::AwaitingInput),
+        Some(&value) => {
+          self.write(1, value)?;
+          self.input.pop_front();
+          self.position += 2;
+        },
+      },
+      4 => {
+        let value = self.read(1)?;
+        self.output.push_back(value);
+        self.position += 2;
+      },
+      5 | 6 => {
+        let (value, target) = (self.read(1)?, self.read(2)?);
+        if (value != 0) == (opcode == 5) {
+          self.position = self.address(target)?;
+        } else {
+          self.position += 3;
+        }
+      },
+      9 => {
+        let offset = self.read(1)?;
+        self.relative_base =
+          self.relative_base.checked_add(offset).ok_or(overflow)?;
+        self.position += 2;
+      },
+      99 => self.halted = true,
+      _ =>
+        return Err(IntcodeError::UnknownOpcode {
+          opcode: self.get(self.position),
+          position: self.position,
+        }),
+    }
+
+    self.instruction_count += 1;
+    Ok(if self.halted { Status::Halted } else { Status::Running })
+  }
+
+  /// Steps the program until it halts or needs more input.
+  ///
+  /// # Errors
+  ///
+  /// Returns the first `IntcodeError` raised by `step`.
+  pub fn run(&mut self) -> Result<Status, IntcodeError> {
+    loop {
+      match self.step()? {
+        Status::Running => (),
+        status => return Ok(status),
+      }
+    }
+  }
+}
+
+impl FromStr for Model {
+  type Err = ParseError;
+
+  /// Reads a program such as `1,0,0,0,99`. Whitespace around values is
+  /// ignored, as are empty values, so a trailing comma or newline is fine.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let int_code = s
+      .split(',')
+      .map(str::trim)
+      .filter(|v| !v.is_empty())
+      .enumerate()
+      .map(|(address, v)| {
+        v.parse().map_err(|_| ParseError { address, value: v.to_string() })
+      })
+      .collect::<Result<_, _>>()?;
+    Ok(Self { int_code, ..Self::default() })
   }
 }
 
@@ -19,57 +331,49 @@ impl From<String> for Model {
   fn from(s: String) -> Self { s.as_str().into() }
 }
 
+#[allow(clippy::fallible_impl_from)]
 impl From<&str> for Model {
-  /// Converts a &str to a Model using the following steps:
-  /// - Remove all non-digits and non-commas from the input string
-  /// - Remove empty inputs (i.e. no ',,'-style scenarios allowed)
-  /// - Convert the remaining inputs to `i64` values
-  /// - Collect as a `Vec<i64>` for `Model.int_code`
+  /// Converts a &str to a Model with `Model::from_str`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a value is not an `i64`. Use `str::parse` to get the
+  /// `ParseError` instead.
   fn from(s: &str) -> Self {
-    Model {
-      int_code: s
-        .chars()
-        .filter(|c| c.eq(&',') || c.is_ascii_digit())
-        .collect::<String>()
-        .split(",")
-        .filter(|s| !s.is_empty())
-        .map(|s| s.parse::<i64>().unwrap())
-        .collect(),
-    }
+    s.parse().unwrap_or_else(|e| panic!("Not an Intcode program: {}", e))
+  }
+}
+
+/// Number of memory cells, opcode included, taken by an instruction.
+#[must_use]
+pub const fn instruction_length(opcode: i64) -> usize {
+  match opcode % 100 {
+    1 | 2 | 7 | 8 => 4,
+    5 | 6 => 3,
+    3 | 4 | 9 => 2,
+    _ => 1,
   }
 }
 
 /// Executes the operation for Opcode 1.
-fn opcode_one(number1: i64, number2: i64) -> i64 { number1 + number2 }
+const fn opcode_one(number1: i64, number2: i64) -> Option<i64> {
+  number1.checked_add(number2)
+}
 
 /// Executes the operation for Opcode 2.
-fn opcode_two(number1: i64, number2: i64) -> i64 { number1 * number2 }
+const fn opcode_two(number1: i64, number2: i64) -> Option<i64> {
+  number1.checked_mul(number2)
+}
 
 /// The executor for Problem 2a.
+///
+/// # Panics
+///
+/// Panics if the program fails with an `IntcodeError`.
+#[must_use]
 pub fn problem_2a(mut m: Model) -> Model {
-  let mut position: usize = 0;
-
-  while m.get(position) != 99 {
-    let opcode = m.get(position);
-    let num1 = m.get(m.get(position + 1) as usize);
-    let num2 = m.get(m.get(position + 2) as usize);
-    let target_slot = m.get(position + 3) as usize;
-
-    let result = match opcode {
-      1 => opcode_one(num1, num2),
-      2 => opcode_two(num1, num2),
-      _ => panic!(format!(
-        "Bad input string, for line opcode:{} num1:{} num2:{} target_slot:{} \
-         at position:{}",
-        opcode, num1, num2, target_slot, position
-      )),
-    };
-
-    // Save the result in the target location.
-    m.set(target_slot, result);
-
-    // Move the position forward by 4 slots.
-    position += 4;
+  if let Err(e) = m.run() {
+    panic!("Bad input string, {}", e);
   }
 
   // Return the updated model.
@@ -1,77 +1,57 @@
-use aoc2019_2::*;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-/// Read the input file and turn into a `Model`.
-fn read_input() -> Model {
-  // Define the filename.
-  let filename = "src/input_2a";
-
-  // Open the file in read-only mode, ignoring errors.
-  let file = File::open(filename).unwrap();
-
-  // Open reader for the file.
-  let mut reader = BufReader::new(file);
-
-  // Read the first and only line.
-  let mut buffer: String = "".into();
-  let _ = reader.read_line(&mut buffer);
-  buffer.into()
-}
-
-/// Execute Problem 2a
-fn aoc2019_2a() -> () {
-  let mut m = read_input();
-
-  // Problem 2a instructions
-  m.set(1, 12);
-  m.set(2, 2);
-
-  // Execute the Intcode program.
-  let m = problem_2a(m);
-
-  // Print the answer for Problem 2a.
-  println!("Problem 2a: {}", m.get(0));
+use aoc2019_2::Model;
+use aoc2019_2::cli::{USAGE, execute, find_noun_verb, parse_args};
+use std::io::Read;
+use std::process::exit;
+
+/// Reads the program from `path`, or from stdin if there is no path.
+fn read_program(path: Option<&str>) -> Result<Model, String> {
+  let name = path.unwrap_or("stdin");
+  let source = if let Some(path) = path {
+    std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?
+  } else {
+    let mut buffer = String::new();
+    std::io::stdin()
+      .read_to_string(&mut buffer)
+      .map_err(|e| format!("stdin: {e}"))?;
+    buffer
+  };
+  source.parse().map_err(|e| format!("{name}: {e}"))
 }
 
-/// Execute Problem 2b
-fn aoc2019_2b() -> () {
-  // Problem 2b instructions
-  // Loop parameter
-  let mut position_1_value: i64 = 0;
-  while position_1_value < 100 {
-    let mut position_2_value: i64 = 0;
-    while position_2_value < 100 {
-      // Reset input Intcode for each execution
-      let mut m = read_input();
-
-      // Change values in Intcode.
-      m.set(1, position_1_value);
-      m.set(2, position_2_value);
-
-      // Execute the Intcode.
-      let m = problem_2a(m);
-
-      // Output if the answer is found.
-      if m.get(0) == 19690720 {
-        println!(
-          "Problem 2b: noun: {}, verb: {}, answer: {}",
-          position_1_value,
-          position_2_value,
-          100 * position_1_value + position_2_value
-        );
-      }
-
-      // Move forward with Position 2 value.
-      position_2_value += 1;
+fn main() {
+  let args: Vec<String> = std::env::args().skip(1).collect();
+  let options = match parse_args(&args) {
+    Ok(options) if options.help => {
+      println!("{USAGE}");
+      return;
+    },
+    Ok(options) => options,
+    Err(e) => {
+      eprintln!("{e}\n\n{USAGE}");
+      exit(2);
+    },
+  };
+
+  let program = match read_program(options.program.as_deref()) {
+    Ok(program) => program,
+    Err(e) => {
+      eprintln!("{e}");
+      exit(2);
+    },
+  };
+
+  if let Some(target) = options.find {
+    if let Some((noun, verb)) = find_noun_verb(&program, &options, target) {
+      let answer = 100 * noun + verb;
+      println!("noun: {noun}, verb: {verb}, answer: {answer}");
+    } else {
+      eprintln!("no noun and verb give {target}");
+      exit(1);
     }
-
-    // Move forward with Position 1 value.
-    position_1_value += 1;
+    return;
   }
-}
 
-fn main() {
-  aoc2019_2a();
-  aoc2019_2b();
+  let outcome = execute(program, &options);
+  println!("{}", outcome.render(options.format));
+  exit(outcome.stop.exit_code());
 }
@@ -0,0 +1,219 @@
+use crate::{IntcodeError, Model, Status};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// One value crossing the boundary of the machine.
+///
+/// `step` is the value of `Model.instruction_count` just before the instruction
+/// that consumed or produced the value, i.e. the 0-based index of that
+/// instruction.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Event {
+  Input { step: u64, value: i64 },
+  Output { step: u64, value: i64 },
+}
+
+impl Event {
+  /// The instruction count at which the event happened.
+  #[must_use]
+  pub const fn step(&self) -> u64 {
+    match self {
+      Self::Input { step, .. } | Self::Output { step, .. } => *step,
+    }
+  }
+}
+
+impl Display for Event {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Input { step, value } => write!(f, "input {value} at step {step}"),
+      Self::Output { step, value } =>
+        write!(f, "output {value} at step {step}"),
+    }
+  }
+}
+
+/// Every I/O event of a session, in the order they happened.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Recording {
+  pub events: Vec<Event>,
+}
+
+impl Recording {
+  /// The recorded inputs, in the order they were consumed.
+  pub fn inputs(&self) -> impl Iterator<Item = i64> + '_ {
+    self.events.iter().filter_map(|e| match e {
+      Event::Input { value, .. } => Some(*value),
+      Event::Output { .. } => None,
+    })
+  }
+
+  /// The recorded outputs, in the order they were produced.
+  pub fn outputs(&self) -> impl Iterator<Item = i64> + '_ {
+    self.events.iter().filter_map(|e| match e {
+      Event::Output { value, .. } => Some(*value),
+      Event::Input { .. } => None,
+    })
+  }
+
+  /// Encodes the recording as JSON.
+  ///
+  /// # Panics
+  ///
+  /// Never in practice: a recording only holds integers.
+  #[must_use]
+  pub fn to_json(&self) -> String { serde_json::to_string(self).unwrap() }
+
+  /// Decodes a recording written by `to_json`.
+  ///
+  /// # Errors
+  ///
+  /// Returns the `serde_json` error if the JSON is not a recording.
+  pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+    serde_json::from_str(s)
+  }
+}
+
+/// Wraps a `Model` and logs every value it reads from `input` and writes to
+/// `output`.
+#[derive(Clone, Debug, Default)]
+pub struct Recorder {
+  model: Model,
+  recording: Recording,
+}
+
+impl Recorder {
+  #[must_use]
+  pub fn new(model: Model) -> Self {
+    Self { model, recording: Recording::default() }
+  }
+
+  #[must_use]
+  pub const fn model(&self) -> &Model { &self.model }
+
+  #[must_use]
+  pub const fn recording(&self) -> &Recording { &self.recording }
+
+  /// Returns the wrapped model and everything recorded so far.
+  #[must_use]
+  pub fn into_parts(self) -> (Model, Recording) { (self.model, self.recording) }
+
+  /// Queues a value for the program to read.
+  pub fn push_input(&mut self, value: i64) { self.model.input.push_back(value) }
+
+  /// Executes one instruction, recording any I/O it performs.
+  ///
+  /// # Errors
+  ///
+  /// Returns the `IntcodeError` raised by `Model::step`.
+  pub fn step(&mut self) -> Result<Status, IntcodeError> {
+    let step = self.model.instruction_count;
+    let next_input = self.model.input.front().copied();
+    let (inputs, outputs) = (self.model.input.len(), self.model.output.len());
+
+    let status = self.model.step()?;
+
+    if let (true, Some(value)) = (self.model.input.len() < inputs, next_input) {
+      self.recording.events.push(Event::Input { step, value });
+    }
+    if self.model.output.len() > outputs {
+      if let Some(&value) = self.model.output.back() {
+        self.recording.events.push(Event::Output { step, value });
+      }
+    }
+    Ok(status)
+  }
+
+  /// Steps the program until it halts or needs more input.
+  ///
+  /// # Errors
+  ///
+  /// Returns the first `IntcodeError` raised by `step`.
+  pub fn run(&mut self) -> Result<Status, IntcodeError> {
+    loop {
+      match self.step()? {
+        Status::Running => (),
+        status => return Ok(status),
+      }
+    }
+  }
+}
+
+/// The first point at which a replay stopped matching its recording.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReplayError {
+  /// The program performed different I/O from the recording. `expected` is
+  /// `None` if the program did more I/O than was recorded, and `actual` is
+  /// `None` if the program stopped before doing all the recorded I/O.
+  Diverged { step: u64, expected: Option<Event>, actual: Option<Event> },
+  /// The program failed with an error at `step`.
+  Machine { step: u64, error: IntcodeError },
+}
+
+impl Display for ReplayError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let describe = |e: &Option<Event>| {
+      e.as_ref().map_or_else(|| "nothing".into(), ToString::to_string)
+    };
+    match self {
+      Self::Diverged { step, expected, actual } => write!(
+        f,
+        "replay diverged at step {}: expected {}, got {}",
+        step,
+        describe(expected),
+        describe(actual)
+      ),
+      Self::Machine { step, error } =>
+        write!(f, "replay failed at step {step}: {error}"),
+    }
+  }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Runs `model` with the inputs from `recording` and checks that it performs
+/// exactly the recorded I/O, at the recorded steps. `model` must be in the
+/// state the recording started from.
+///
+/// # Errors
+///
+/// Returns a `ReplayError` describing the first difference from the
+/// recording.
+pub fn replay(
+  model: Model,
+  recording: &Recording,
+) -> Result<Model, ReplayError> {
+  let mut recorder = Recorder::new(model);
+  recording.inputs().for_each(|v| recorder.push_input(v));
+
+  let mut checked = 0;
+  loop {
+    let step = recorder.model.instruction_count;
+    let status =
+      recorder.step().map_err(|error| ReplayError::Machine { step, error })?;
+
+    let actual = &recorder.recording.events;
+    while checked < actual.len() {
+      let expected = recording.events.get(checked).copied();
+      if expected != Some(actual[checked]) {
+        return Err(ReplayError::Diverged {
+          step,
+          expected,
+          actual: Some(actual[checked]),
+        });
+      }
+      checked += 1;
+    }
+
+    if status != Status::Running {
+      return match recording.events.get(checked) {
+        Some(&expected) => Err(ReplayError::Diverged {
+          step: recorder.model.instruction_count,
+          expected: Some(expected),
+          actual: None,
+        }),
+        None => Ok(recorder.model),
+      };
+    }
+  }
+}
@@ -0,0 +1,294 @@
+use crate::Model;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Version written by this crate. Loading rejects any other version.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// First bytes of every binary saved state.
+pub const MAGIC: &[u8; 4] = b"ICMS";
+
+/// The on-disk encoding of a saved state.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+  Json,
+  Binary,
+}
+
+/// Reasons a saved state could not be written or read back.
+#[derive(Debug)]
+pub enum StateError {
+  Io(std::io::Error),
+  Json(serde_json::Error),
+  /// Binary data that does not start with `MAGIC`.
+  BadMagic,
+  /// The version in the file is not `FORMAT_VERSION`.
+  UnsupportedVersion(u64),
+  /// The binary data ended in the middle of a field.
+  Truncated,
+  /// A binary field does not fit in its target type.
+  Overflow,
+  /// The binary data has bytes left over after the last field.
+  TrailingBytes,
+}
+
+impl Display for StateError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Io(e) => write!(f, "I/O error: {e}"),
+      Self::Json(e) => write!(f, "invalid JSON state: {e}"),
+      Self::UnsupportedVersion(v) =>
+        write!(f, "unsupported state version {v} (expected {FORMAT_VERSION})"),
+      Self::BadMagic => write!(f, "binary state does not start with {MAGIC:?}"),
+      Self::Truncated => write!(f, "binary state is truncated"),
+      Self::Overflow => write!(f, "binary state has an out-of-range value"),
+      Self::TrailingBytes => write!(f, "binary state has trailing bytes"),
+    }
+  }
+}
+
+impl std::error::Error for StateError {}
+
+impl From<std::io::Error> for StateError {
+  fn from(e: std::io::Error) -> Self { Self::Io(e) }
+}
+
+impl From<serde_json::Error> for StateError {
+  fn from(e: serde_json::Error) -> Self { Self::Json(e) }
+}
+
+/// The fields of version 1 of the format, in the order they are stored.
+#[derive(Deserialize, Serialize)]
+struct SavedState {
+  version: u32,
+  memory: Vec<i64>,
+  position: u64,
+  relative_base: i64,
+  instruction_count: u64,
+  input: Vec<i64>,
+  output: Vec<i64>,
+  halted: bool,
+}
+
+impl From<&Model> for SavedState {
+  fn from(m: &Model) -> Self {
+    Self {
+      version: FORMAT_VERSION,
+      memory: m.int_code.clone(),
+      position: m.position as u64,
+      relative_base: m.relative_base,
+      instruction_count: m.instruction_count,
+      input: m.input.iter().copied().collect(),
+      output: m.output.iter().copied().collect(),
+      halted: m.halted,
+    }
+  }
+}
+
+impl SavedState {
+  fn into_model(self) -> Result<Model, StateError> {
+    Ok(Model {
+      int_code: self.memory,
+      position: usize::try_from(self.position)
+        .map_err(|_| StateError::Overflow)?,
+      relative_base: self.relative_base,
+      input: self.input.into(),
+      output: self.output.into(),
+      halted: self.halted,
+      instruction_count: self.instruction_count,
+    })
+  }
+}
+
+/// Encodes a model as a JSON saved state.
+///
+/// # Panics
+///
+/// Never in practice: serialising plain integers, vectors and booleans cannot
+/// fail.
+#[must_use]
+pub fn to_json(m: &Model) -> String {
+  serde_json::to_string(&SavedState::from(m)).unwrap()
+}
+
+/// Decodes a JSON saved state.
+///
+/// # Errors
+///
+/// Returns a `StateError` if the JSON is malformed or has another version.
+pub fn from_json(s: &str) -> Result<Model, StateError> {
+  // Read the version on its own first, so that a future format with
+  // different fields is reported as a version mismatch.
+  let value: serde_json::Value = serde_json::from_str(s)?;
+  match value.get("version").and_then(serde_json::Value::as_u64) {
+    Some(v) if v == u64::from(FORMAT_VERSION) =>
+      serde_json::from_value::<SavedState>(value)?.into_model(),
+    Some(v) => Err(StateError::UnsupportedVersion(v)),
+    None => Err(StateError::UnsupportedVersion(0)),
+  }
+}
+
+/// Appends `value` as an unsigned LEB128 integer.
+fn put_unsigned(buffer: &mut Vec<u8>, mut value: u64) {
+  while value >= 0x80 {
+    buffer.push((value.to_le_bytes()[0] & 0x7f) | 0x80);
+    value >>= 7;
+  }
+  buffer.push(value.to_le_bytes()[0]);
+}
+
+/// Appends `value` as a zigzag-encoded LEB128 integer.
+fn put_signed(buffer: &mut Vec<u8>, value: i64) {
+  put_unsigned(buffer, ((value << 1) ^ (value >> 63)).cast_unsigned());
+}
+
+/// Appends a length-prefixed list of signed integers.
+fn put_list<'a>(
+  buffer: &mut Vec<u8>,
+  values: impl ExactSizeIterator<Item = &'a i64>,
+) {
+  put_unsigned(buffer, values.len() as u64);
+  values.for_each(|v| put_signed(buffer, *v));
+}
+
+/// Encodes a model as a binary saved state.
+#[must_use]
+pub fn to_binary(m: &Model) -> Vec<u8> {
+  let mut buffer = MAGIC.to_vec();
+  put_unsigned(&mut buffer, u64::from(FORMAT_VERSION));
+  put_list(&mut buffer, m.int_code.iter());
+  put_unsigned(&mut buffer, m.position as u64);
+  put_signed(&mut buffer, m.relative_base);
+  put_unsigned(&mut buffer, m.instruction_count);
+  put_list(&mut buffer, m.input.iter());
+  put_list(&mut buffer, m.output.iter());
+  buffer.push(u8::from(m.halted));
+  buffer
+}
+
+/// Cursor over the bytes of a binary saved state.
+struct Reader<'a> {
+  bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+  fn byte(&mut self) -> Result<u8, StateError> {
+    let (first, rest) =
+      self.bytes.split_first().ok_or(StateError::Truncated)?;
+    self.bytes = rest;
+    Ok(*first)
+  }
+
+  fn unsigned(&mut self) -> Result<u64, StateError> {
+    let mut value: u64 = 0;
+    for shift in (0..64).step_by(7) {
+      let byte = self.byte()?;
+      let bits = u64::from(byte & 0x7f);
+      if shift == 63 && bits > 1 {
+        return Err(StateError::Overflow);
+      }
+      value |= bits << shift;
+      if byte & 0x80 == 0 {
+        return Ok(value);
+      }
+    }
+    Err(StateError::Overflow)
+  }
+
+  fn signed(&mut self) -> Result<i64, StateError> {
+    let value = self.unsigned()?;
+    Ok((value >> 1).cast_signed() ^ -(value & 1).cast_signed())
+  }
+
+  fn list(&mut self) -> Result<Vec<i64>, StateError> {
+    let len = self.unsigned()?;
+    // Every entry takes at least one byte, which bounds the allocation.
+    if len > self.bytes.len() as u64 {
+      return Err(StateError::Truncated);
+    }
+    (0..len).map(|_| self.signed()).collect()
+  }
+}
+
+/// Decodes a binary saved state.
+///
+/// # Errors
+///
+/// Returns a `StateError` if the data does not start with `MAGIC`, has
+/// another version, or is malformed.
+pub fn from_binary(bytes: &[u8]) -> Result<Model, StateError> {
+  if !bytes.starts_with(MAGIC) {
+    return Err(StateError::BadMagic);
+  }
+  let mut reader = Reader { bytes: &bytes[MAGIC.len()..] };
+  let version = reader.unsigned()?;
+  if version != u64::from(FORMAT_VERSION) {
+    return Err(StateError::UnsupportedVersion(version));
+  }
+
+  let state = SavedState {
+    version: FORMAT_VERSION,
+    memory: reader.list()?,
+    position: reader.unsigned()?,
+    relative_base: reader.signed()?,
+    instruction_count: reader.unsigned()?,
+    input: reader.list()?,
+    output: reader.list()?,
+    halted: reader.byte()? != 0,
+  };
+  if reader.bytes.is_empty() {
+    state.into_model()
+  } else {
+    Err(StateError::TrailingBytes)
+  }
+}
+
+/// Encodes a model in the given format.
+#[must_use]
+pub fn to_bytes(m: &Model, format: Format) -> Vec<u8> {
+  match format {
+    Format::Json => to_json(m).into_bytes(),
+    Format::Binary => to_binary(m),
+  }
+}
+
+/// Decodes a saved state in either format, using `MAGIC` to tell them apart.
+///
+/// # Errors
+///
+/// Returns a `StateError` if the data is not a valid saved state.
+pub fn from_bytes(bytes: &[u8]) -> Result<Model, StateError> {
+  if bytes.starts_with(MAGIC) {
+    from_binary(bytes)
+  } else {
+    let s = std::str::from_utf8(bytes).map_err(|e| {
+      StateError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    })?;
+    from_json(s)
+  }
+}
+
+/// Writes the state of a model to a file.
+///
+/// # Errors
+///
+/// Returns a `StateError` if the file cannot be written.
+pub fn save(
+  m: &Model,
+  path: impl AsRef<Path>,
+  format: Format,
+) -> Result<(), StateError> {
+  Ok(std::fs::write(path, to_bytes(m, format))?)
+}
+
+/// Reads a model back from a file written by `save`, in either format.
+///
+/// # Errors
+///
+/// Returns a `StateError` if the file cannot be read or is not a valid saved
+/// state.
+pub fn load(path: impl AsRef<Path>) -> Result<Model, StateError> {
+  from_bytes(&std::fs::read(path)?)
+}
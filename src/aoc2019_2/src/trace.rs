@@ -0,0 +1,121 @@
+use crate::{IntcodeError, Model, Status};
+use std::fmt::{Display, Formatter};
+
+/// What a single call to `step` did.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Operation {
+  /// `Model.instruction_count` before the operation.
+  pub step: u64,
+  /// Position of the instruction.
+  pub position: usize,
+  /// Opcode of the instruction, without parameter modes.
+  pub opcode: i64,
+  /// Values of the parameters the instruction read, after applying modes.
+  pub arguments: Vec<i64>,
+  /// Address and value written to memory, if any.
+  pub write: Option<(usize, i64)>,
+  /// The model's state after the operation.
+  pub status: Status,
+  /// Instruction pointer after the operation.
+  pub next_position: usize,
+  /// Relative base after the operation.
+  pub relative_base: i64,
+}
+
+impl Display for Operation {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let args = &self.arguments;
+    match (self.opcode, self.write, self.status) {
+      (3, _, Status::AwaitingInput) =>
+        write!(f, "Waiting for input at position {}", self.position),
+      (99, ..) => write!(
+        f,
+        "Program has ended based on code 99 at position {}",
+        self.position
+      ),
+      (4, ..) => write!(f, "Output {} (OpCode 4)", args[0]),
+      (9, ..) => write!(
+        f,
+        "Moved relative base to {} (OpCode 9 [{}])",
+        self.relative_base, args[0]
+      ),
+      (5 | 6, ..) => write!(
+        f,
+        "Moved to position {} (OpCode {} [{}, {}])",
+        self.next_position, self.opcode, args[0], args[1]
+      ),
+      (3, Some((address, value)), _) =>
+        write!(f, "Placing {value} (OpCode 3 input) into position {address}"),
+      (_, Some((address, value)), _) => write!(
+        f,
+        "Placing {} (OpCode {} [{}, {}]) into position {}",
+        value, self.opcode, args[0], args[1], address
+      ),
+      _ => write!(f, "OpCode {} at position {}", self.opcode, self.position),
+    }
+  }
+}
+
+/// Executes one instruction with `Model::step` and describes what it did.
+///
+/// # Errors
+///
+/// Returns the `IntcodeError` raised by `Model::step`, or by decoding the
+/// instruction's parameters.
+pub fn step(m: &mut Model) -> Result<Operation, IntcodeError> {
+  if m.halted {
+    return Err(IntcodeError::Halted);
+  }
+
+  let (step, position) = (m.instruction_count, m.position);
+  let opcode = m.get(position) % 100;
+  let reads = match opcode {
+    1 | 2 | 5 | 6 | 7 | 8 => 2,
+    4 | 9 => 1,
+    _ => 0,
+  };
+  let arguments =
+    (1..=reads).map(|n| m.read(n)).collect::<Result<Vec<_>, _>>()?;
+  let target = match opcode {
+    1 | 2 | 7 | 8 => Some(m.parameter_address(3)?),
+    3 if !m.input.is_empty() => Some(m.parameter_address(1)?),
+    _ => None,
+  };
+
+  let status = m.step()?;
+  let write = match status {
+    Status::AwaitingInput => None,
+    _ => target.map(|address| (address, m.get(address))),
+  };
+
+  Ok(Operation {
+    step,
+    position,
+    opcode,
+    arguments,
+    write,
+    status,
+    next_position: m.position,
+    relative_base: m.relative_base,
+  })
+}
+
+/// Runs the model to completion, or until it needs input, and returns every
+/// operation performed.
+///
+/// # Errors
+///
+/// Returns the first `IntcodeError` raised by `step`.
+pub fn run(m: &mut Model) -> Result<Vec<Operation>, IntcodeError> {
+  let mut operations = Vec::new();
+  loop {
+    let operation = step(m)?;
+    let status = operation.status;
+    if status != Status::AwaitingInput {
+      operations.push(operation);
+    }
+    if status != Status::Running {
+      return Ok(operations);
+    }
+  }
+}
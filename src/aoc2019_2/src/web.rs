@@ -0,0 +1,250 @@
+use crate::trace;
+use crate::{Model, Status, instruction_length};
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use web_sys::{
+  Document, Event, HtmlButtonElement, HtmlInputElement, HtmlTextAreaElement,
+};
+
+/// Values shown in, or entered through, the page.
+#[derive(Debug, Default)]
+pub struct UiModel {
+  pub input: String,
+  pub operations_history: String,
+  pub process_button_disabled: bool,
+  pub visual_delay_ms: u32,
+}
+
+/// The Intcode program being visualised, and the page around it.
+#[derive(Debug, Default)]
+pub struct WebModel {
+  pub model: Model,
+  pub ui_model: UiModel,
+}
+
+impl WebModel {
+  fn log(&mut self, s: &str) {
+    self.ui_model.operations_history += s;
+    self.ui_model.operations_history += "\n";
+  }
+}
+
+/// Messages.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Msg {
+  InputChanged(String),
+  DelayChanged(String),
+  ProcessInputClicked,
+  LogicInitialize,
+  LogicProcessCurrentOpCode,
+  LogicFinalize,
+}
+
+/// The next message to handle, if any, returned by `update`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Order {
+  /// Handle the message straight away.
+  Now(Msg),
+  /// Handle the message after the given number of milliseconds.
+  After(Msg, u32),
+}
+
+/// Update function. Execution is delegated to `trace::step`, so the page
+/// always agrees with the library.
+pub fn update(msg: Msg, web: &mut WebModel) -> Option<Order> {
+  match msg {
+    Msg::InputChanged(s) => {
+      web.ui_model.input = s
+        .chars()
+        .filter(|c| c.eq(&',') || c.eq(&'-') || c.is_ascii_digit())
+        .collect();
+      None
+    },
+    Msg::DelayChanged(s) => {
+      if let Ok(delay) = s.trim().parse() {
+        web.ui_model.visual_delay_ms = delay;
+      }
+      None
+    },
+    Msg::ProcessInputClicked => {
+      web.ui_model.process_button_disabled = true;
+      Some(Order::Now(Msg::LogicInitialize))
+    },
+    Msg::LogicInitialize => {
+      web.ui_model.operations_history = String::new();
+      match web.ui_model.input.parse() {
+        Ok(model) => {
+          web.model = model;
+          web.log("Initializing Logic");
+          Some(Order::After(
+            Msg::LogicProcessCurrentOpCode,
+            web.ui_model.visual_delay_ms,
+          ))
+        },
+        Err(e) => {
+          web.model = Model::default();
+          web.log(&format!("Error, {e}"));
+          Some(Order::Now(Msg::LogicFinalize))
+        },
+      }
+    },
+    Msg::LogicProcessCurrentOpCode => match trace::step(&mut web.model) {
+      Ok(operation) => {
+        web.log(&operation.to_string());
+        if operation.status == Status::Running {
+          Some(Order::After(
+            Msg::LogicProcessCurrentOpCode,
+            web.ui_model.visual_delay_ms,
+          ))
+        } else {
+          Some(Order::Now(Msg::LogicFinalize))
+        }
+      },
+      Err(e) => {
+        web.log(&format!("Error, {e}"));
+        Some(Order::Now(Msg::LogicFinalize))
+      },
+    },
+    Msg::LogicFinalize => {
+      web.ui_model.process_button_disabled = false;
+      None
+    },
+  }
+}
+
+/// The memory grid as HTML, with the cells of the current instruction in
+/// bold.
+#[must_use]
+pub fn view_memory(model: &Model) -> String {
+  let current = model.position
+    ..model.position + instruction_length(model.get(model.position));
+  model
+    .int_code
+    .iter()
+    .enumerate()
+    .map(|(i, v)| {
+      if current.contains(&i) {
+        format!("<label style=\"margin: 5px\"><strong>{v}</strong></label>")
+      } else {
+        format!("<label style=\"margin: 5px\">{v}</label>")
+      }
+    })
+    .collect()
+}
+
+/// The page layout: input, controls and memory on the left, history on the
+/// right. `view` fills in the values.
+const LAYOUT: &str = r#"
+<div style="display: flex; flex-direction: row; flex-wrap: wrap">
+  <div style="display: flex; flex-direction: column; width: 50%">
+    <label for="input-area">Problem Input</label>
+    <textarea id="input-area" autocomplete="off" autofocus wrap="soft"></textarea>
+    <label for="delay">Delay (ms)</label>
+    <input id="delay" type="number" min="0">
+    <button id="process-button">Process Input</button>
+    <label id="answer"></label>
+    <div id="memory" style="display: flex; flex-direction: row; flex-wrap: wrap"></div>
+  </div>
+  <div style="display: flex; flex-direction: column; width: 50%">
+    <label for="history-area">History</label>
+    <textarea id="history-area" readonly rows="50" wrap="soft"></textarea>
+  </div>
+</div>
+"#;
+
+thread_local! {
+  static APP: RefCell<WebModel> = RefCell::new(WebModel::default());
+}
+
+fn document() -> Document {
+  web_sys::window().and_then(|w| w.document()).expect("no document")
+}
+
+fn element<T: JsCast>(id: &str) -> T {
+  document()
+    .get_element_by_id(id)
+    .and_then(|e| e.dyn_into::<T>().ok())
+    .unwrap_or_else(|| panic!("missing element #{}", id))
+}
+
+/// Copies the model onto the page.
+fn view(web: &WebModel) {
+  let ui = &web.ui_model;
+  element::<HtmlTextAreaElement>("history-area")
+    .set_value(&ui.operations_history);
+  element::<HtmlButtonElement>("process-button")
+    .set_disabled(ui.process_button_disabled || ui.input.is_empty());
+  element::<web_sys::HtmlElement>("answer").set_inner_text(&format!(
+    "Answer: {}",
+    web.model.int_code.first().map(ToString::to_string).unwrap_or_default()
+  ));
+  element::<web_sys::Element>("memory")
+    .set_inner_html(&view_memory(&web.model));
+}
+
+/// Runs `update` and `view` for a message, then follows the returned order.
+fn dispatch(msg: Msg) {
+  let mut next = Some(Order::Now(msg));
+  while let Some(order) = next.take() {
+    match order {
+      Order::Now(msg) =>
+        next = APP.with(|app| {
+          let mut web = app.borrow_mut();
+          let order = update(msg, &mut web);
+          view(&web);
+          order
+        }),
+      Order::After(msg, delay_ms) => {
+        let callback = Closure::once_into_js(move || dispatch(msg));
+        let _ = web_sys::window()
+          .expect("no window")
+          .set_timeout_with_callback_and_timeout_and_arguments_0(
+            callback.unchecked_ref(),
+            i32::try_from(delay_ms).unwrap_or(i32::MAX),
+          );
+      },
+    }
+  }
+}
+
+/// Calls `dispatch` with the message built from an element's events.
+fn listen(id: &str, event: &str, to_msg: impl Fn() -> Msg + 'static) {
+  let callback =
+    Closure::<dyn Fn(Event)>::new(move |_: Event| dispatch(to_msg()));
+  document()
+    .get_element_by_id(id)
+    .expect("missing element")
+    .add_event_listener_with_callback(event, callback.as_ref().unchecked_ref())
+    .expect("unable to add listener");
+  callback.forget();
+}
+
+/// Entry point: builds the page inside `#app` and wires up its controls.
+///
+/// # Panics
+///
+/// Panics if the page has no `#app` element.
+#[wasm_bindgen(start)]
+pub fn render() {
+  document()
+    .get_element_by_id("app")
+    .expect("missing #app")
+    .set_inner_html(LAYOUT);
+
+  listen("input-area", "input", || {
+    Msg::InputChanged(element::<HtmlTextAreaElement>("input-area").value())
+  });
+  listen("delay", "input", || {
+    Msg::DelayChanged(element::<HtmlInputElement>("delay").value())
+  });
+  listen("process-button", "click", || Msg::ProcessInputClicked);
+
+  APP.with(|app| {
+    let web = app.borrow();
+    element::<HtmlInputElement>("delay")
+      .set_value(&web.ui_model.visual_delay_ms.to_string());
+    view(&web);
+  });
+}
@@ -0,0 +1,163 @@
+/// Tests for the command line runner.
+#[cfg(test)]
+mod tests_aoc2019_2_cli {
+  use aoc2019_2::cli::*;
+  use aoc2019_2::*;
+
+  fn args(s: &str) -> Vec<String> {
+    s.split_whitespace().map(String::from).collect()
+  }
+
+  #[test]
+  fn parse_defaults() {
+    assert_eq!(parse_args(&[]), Ok(Options::default()));
+    assert_eq!(parse_args(&args("-")), Ok(Options::default()));
+  }
+
+  #[test]
+  fn parse_all_options() {
+    let options = parse_args(&args(
+      "src/input_2a --set 1=12 --set 2=2 --input 1,-2 --input 3 --format json \
+       --limit 100 --find 19690720",
+    ))
+    .unwrap();
+    assert_eq!(options, Options {
+      program: Some("src/input_2a".into()),
+      patches: vec![(1, 12), (2, 2)],
+      input: vec![1, -2, 3],
+      format: OutputFormat::Json,
+      limit: Some(100),
+      find: Some(19_690_720),
+      help: false,
+    });
+  }
+
+  #[test]
+  fn parse_errors() {
+    assert!(parse_args(&args("--set 1")).is_err());
+    assert!(parse_args(&args("--set a=1")).is_err());
+    assert!(parse_args(&args("--input 1,x")).is_err());
+    assert!(parse_args(&args("--format xml")).is_err());
+    assert!(parse_args(&args("--limit")).is_err());
+    assert!(parse_args(&args("--find x")).is_err());
+    assert!(parse_args(&args("--set 9000000000000000000=1")).is_err());
+    assert!(parse_args(&args("--verbose")).is_err());
+    assert!(parse_args(&args("a b")).is_err());
+  }
+
+  #[test]
+  fn unreadable_program_exits_2() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc2019_2"))
+      .stdin(Stdio::piped())
+      .stdout(Stdio::null())
+      .stderr(Stdio::piped())
+      .spawn()
+      .unwrap();
+    child.stdin.take().unwrap().write_all(b"5,-,3").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+      String::from_utf8(output.stderr).unwrap(),
+      "stdin: bad value '-' at address 1\n"
+    );
+  }
+
+  #[test]
+  fn execute_problem_2a() {
+    let program: Model =
+      std::fs::read_to_string("src/input_2a").unwrap().parse().unwrap();
+    let options = parse_args(&args("--set 1=12 --set 2=2")).unwrap();
+    let outcome = execute(program.clone(), &options);
+    assert_eq!(outcome.stop, Stop::Halted);
+    assert_eq!(outcome.stop.exit_code(), 0);
+
+    let mut expected = program;
+    expected.set(1, 12);
+    expected.set(2, 2);
+    assert_eq!(outcome.model.get(0), problem_2a(expected).get(0));
+  }
+
+  #[test]
+  fn find_problem_2b() {
+    let program: Model =
+      std::fs::read_to_string("src/input_2a").unwrap().parse().unwrap();
+    let options = Options::default();
+    let (noun, verb) = find_noun_verb(&program, &options, 19_690_720).unwrap();
+    let mut expected = program.clone();
+    expected.set(1, noun);
+    expected.set(2, verb);
+    assert_eq!(problem_2a(expected).get(0), 19_690_720);
+    assert_eq!(find_noun_verb(&program, &options, -1), None);
+  }
+
+  #[test]
+  fn find_stops_each_attempt() {
+    // Jumps to the verb unless the noun is 0, so a verb of 0 loops forever.
+    let program: Model = "1105,0,0".parse().unwrap();
+    let options = Options::default();
+    assert_eq!(find_noun_verb(&program, &options, -1), None);
+    let options = parse_args(&args("--limit 10")).unwrap();
+    assert_eq!(find_noun_verb(&program, &options, -1), None);
+  }
+
+  #[test]
+  fn execute_stops() {
+    let echo: Model = "3,7,4,7,1105,1,0,0".parse().unwrap();
+
+    let outcome =
+      execute(echo.clone(), &parse_args(&args("--input 5,6")).unwrap());
+    assert_eq!(outcome.stop, Stop::AwaitingInput);
+    assert_eq!(outcome.stop.exit_code(), 4);
+    assert_eq!(outcome.model.output, vec![5, 6]);
+
+    let outcome =
+      execute(echo, &parse_args(&args("--input 5 --limit 2")).unwrap());
+    assert_eq!(outcome.stop, Stop::LimitReached);
+    assert_eq!(outcome.stop.exit_code(), 3);
+    assert_eq!(outcome.model.instruction_count, 2);
+
+    let outcome = execute("42".parse().unwrap(), &Options::default());
+    assert_eq!(
+      outcome.stop,
+      Stop::Error(IntcodeError::UnknownOpcode { opcode: 42, position: 0 })
+    );
+    assert_eq!(outcome.stop.exit_code(), 1);
+  }
+
+  #[test]
+  fn render_plain() {
+    let outcome = execute(
+      "4,0,99".parse().unwrap(),
+      &parse_args(&args("--set 1=2")).unwrap(),
+    );
+    assert_eq!(
+      outcome.render(OutputFormat::Plain).lines().collect::<Vec<_>>(),
+      vec![
+        "99",
+        "halted after 2 instruction(s) at position 2",
+        "Position 0: 4",
+      ]
+    );
+  }
+
+  #[test]
+  fn render_json() {
+    let outcome = execute("4,0,99".parse().unwrap(), &Options::default());
+    let json: serde_json::Value =
+      serde_json::from_str(&outcome.render(OutputFormat::Json)).unwrap();
+    assert_eq!(
+      json,
+      serde_json::json!({
+        "status": "halted",
+        "error": null,
+        "position": 2,
+        "instruction_count": 2,
+        "output": [4],
+        "memory": [4, 0, 99],
+      })
+    );
+  }
+}
@@ -0,0 +1,142 @@
+/// Tests for comparing final models and lockstep runs.
+#[cfg(test)]
+mod tests_aoc2019_2_diff {
+  use aoc2019_2::diff::*;
+  use aoc2019_2::*;
+
+  /// Reads a value, writes 1 to address 9 if it equals 8 and 0 otherwise,
+  /// then outputs the result.
+  const EQUALS_8: &str = "3,9,1008,9,8,9,4,9,99,0";
+
+  #[test]
+  fn compare_identical() {
+    let m: Model = "1,0,0,0,99".parse().unwrap();
+    assert!(compare(&m, &m.clone()).is_empty());
+  }
+
+  #[test]
+  fn compare_different_lengths() {
+    let left: Model = "1,2,3".parse().unwrap();
+    let right: Model = "1,5,3,0,7".parse().unwrap();
+    assert_eq!(compare(&left, &right), vec![
+      AddressDiff { address: 1, left: 2, right: 5 },
+      AddressDiff { address: 4, left: 0, right: 7 },
+    ]);
+  }
+
+  #[test]
+  fn diff_runs_same_input() {
+    let diff = diff_runs(&EQUALS_8.parse().unwrap(), &[8], &[8], 1000);
+    assert_eq!(diff.divergence, None);
+    assert!(diff.memory.is_empty());
+    assert!(diff.left.model.halted && diff.right.model.halted);
+  }
+
+  #[test]
+  fn diff_runs_different_input() {
+    let diff = diff_runs(&EQUALS_8.parse().unwrap(), &[8], &[7], 1000);
+
+    let divergence = diff.divergence.clone().unwrap();
+    assert_eq!(divergence.step, 0);
+    assert_eq!(divergence.left.unwrap().write, Some((9, 8)));
+    assert_eq!(divergence.right.unwrap().write, Some((9, 7)));
+
+    assert_eq!(diff.memory, vec![AddressDiff {
+      address: 9,
+      left: 1,
+      right: 0
+    }]);
+    assert_eq!(diff.watched_writes(), vec![(9, &vec![(0, 8), (1, 1)], &vec![
+      (0, 7),
+      (1, 0)
+    ])]);
+    assert_eq!(diff.left.model.output, vec![1]);
+    assert_eq!(diff.right.model.output, vec![0]);
+  }
+
+  #[test]
+  fn diff_models_with_patches() {
+    let mut left: Model = "1,0,0,0,99".parse().unwrap();
+    let mut right = left.clone();
+    left.set(1, 4);
+    right.set(1, 0);
+    let diff = diff_models(left, right, 1000);
+    assert_eq!(diff.divergence.unwrap().step, 0);
+    assert_eq!(diff.memory, vec![
+      AddressDiff { address: 0, left: 100, right: 2 },
+      AddressDiff { address: 1, left: 4, right: 0 },
+    ]);
+  }
+
+  #[test]
+  fn diff_models_different_instruction_counts() {
+    let program: Model = EQUALS_8.parse().unwrap();
+    let (mut left, mut right) = (program.clone(), program);
+    left.instruction_count = 5;
+    left.input.push_back(8);
+    right.input.push_back(8);
+    let diff = diff_models(left.clone(), right.clone(), 1000);
+    assert_eq!(diff.divergence, None);
+    assert!(diff.memory.is_empty());
+    assert_eq!(diff.left.model.instruction_count, 9);
+    assert_eq!(diff.right.model.instruction_count, 4);
+
+    right.input[0] = 7;
+    let divergence = diff_models(left, right, 1000).divergence.unwrap();
+    assert_eq!(divergence.left.unwrap().step, 5);
+    assert_eq!(divergence.right.unwrap().step, 0);
+  }
+
+  #[test]
+  fn diff_runs_one_side_fails() {
+    // Jumps to address 0 when the input is 0, which holds the input itself.
+    let diff = diff_runs(&"3,0,1006,0,0,99".parse().unwrap(), &[1], &[0], 1000);
+    assert_eq!(diff.left.error, None);
+    assert_eq!(
+      diff.right.error,
+      Some(IntcodeError::UnknownOpcode { opcode: 0, position: 0 })
+    );
+    assert_eq!(diff.divergence.as_ref().map(|d| d.step), Some(0));
+    assert!(diff.left.model.halted);
+    assert_eq!(diff.right.model.position, 0);
+    assert!(
+      diff
+        .to_string()
+        .contains("The right run failed: unknown opcode 0 at position 0")
+    );
+  }
+
+  #[test]
+  fn diff_runs_limit() {
+    // Jumps back to itself forever.
+    let diff = diff_runs(&"1105,1,0".parse().unwrap(), &[], &[], 10);
+    assert!(diff.left.limit_reached && diff.right.limit_reached);
+    assert_eq!(diff.left.model.instruction_count, 10);
+    assert_eq!(diff.divergence, None);
+    assert!(
+      diff.to_string().contains("The left run reached the instruction limit")
+    );
+
+    // The limit counts from where each model already is.
+    let mut left: Model = "1105,1,0".parse().unwrap();
+    left.instruction_count = 5;
+    let diff = diff_models(left, "99".parse().unwrap(), 10);
+    assert_eq!(diff.left.model.instruction_count, 15);
+    assert!(diff.left.limit_reached && !diff.right.limit_reached);
+  }
+
+  #[test]
+  fn report() {
+    let report =
+      diff_runs(&EQUALS_8.parse().unwrap(), &[8], &[7], 1000).to_string();
+    assert_eq!(report.lines().collect::<Vec<_>>(), vec![
+      "Runs diverged at step 0",
+      "  left:  Placing 8 (OpCode 3 input) into position 9 (position 0)",
+      "  right: Placing 7 (OpCode 3 input) into position 9 (position 0)",
+      "1 address(es) differ",
+      "  [9] left 1 right 0",
+      "    left writes:  8@0, 1@1",
+      "    right writes: 7@0, 0@1",
+    ]);
+  }
+}
@@ -0,0 +1,365 @@
+/// Differential tests: random programs are run by a small reference
+/// interpreter and by every executor in the crate, which must all agree.
+/// Valid programs must halt cleanly, and programs that may fail must fail in
+/// the same way everywhere.
+#[cfg(test)]
+mod tests_aoc2019_2_fuzz {
+  use aoc2019_2::cli::{Options, Stop, execute};
+  use aoc2019_2::replay::Recorder;
+  use aoc2019_2::*;
+  use proptest::prelude::*;
+  use proptest::sample::Index;
+  use std::convert::TryFrom;
+
+  /// Number of data cells placed after the halt instruction. Every write
+  /// goes to one of them, so the code itself is never modified.
+  const DATA: usize = 8;
+
+  /// Maximum number of instructions before the halt.
+  const MAX_INSTRUCTIONS: usize = 30;
+
+  /// A parameter that is read. Position parameters may point anywhere in the
+  /// program, code included. Relative parameters, only found in
+  /// `arb_program_with_errors`, hold an address in the program too, but it is
+  /// offset by whatever the relative base is when the instruction runs, so
+  /// they may also read past the end, below zero or overflow.
+  #[derive(Clone, Debug)]
+  enum Param {
+    Position(Index),
+    Immediate(i64),
+    Relative(Index),
+  }
+
+  /// An instruction before its addresses are laid out.
+  #[derive(Clone, Debug)]
+  enum Instruction {
+    /// Opcodes 1, 2, 7 and 8, writing to a data cell.
+    Binary { opcode: i64, left: Param, right: Param, target: Index },
+    /// Opcode 3, writing to a data cell.
+    Input { target: Index },
+    /// Opcode 4.
+    Output { value: Param },
+    /// Opcodes 5 and 6, jumping forward to a later instruction or the halt,
+    /// so that every program terminates.
+    Jump { opcode: i64, condition: Param, target: Index },
+    /// Opcode 9, only found in `arb_program_with_errors`.
+    AdjustBase { offset: Param },
+  }
+
+  /// Small values, so that comparisons are sometimes true.
+  fn arb_small_value() -> impl Strategy<Value = i64> { -9i64..=9 }
+
+  /// Mostly small values, but also any `i64`, so that arithmetic and
+  /// addresses overflow.
+  fn arb_value() -> impl Strategy<Value = i64> {
+    prop_oneof![3 => arb_small_value(), 1 => any::<i64>()]
+  }
+
+  /// Position and small immediate parameters, which are always valid.
+  fn arb_valid_param() -> impl Strategy<Value = Param> {
+    prop_oneof![
+      any::<Index>().prop_map(Param::Position),
+      arb_small_value().prop_map(Param::Immediate),
+    ]
+  }
+
+  /// Parameters in any mode, holding any value.
+  fn arb_param() -> impl Strategy<Value = Param> {
+    prop_oneof![
+      any::<Index>().prop_map(Param::Position),
+      arb_value().prop_map(Param::Immediate),
+      any::<Index>().prop_map(Param::Relative),
+    ]
+  }
+
+  fn arb_binary(
+    opcodes: Vec<i64>,
+    left: impl Strategy<Value = Param>,
+    right: impl Strategy<Value = Param>,
+  ) -> impl Strategy<Value = Instruction> {
+    (prop::sample::select(opcodes), left, right, any::<Index>()).prop_map(
+      |(opcode, left, right, target)| Instruction::Binary {
+        opcode,
+        left,
+        right,
+        target,
+      },
+    )
+  }
+
+  fn arb_jump(
+    condition: impl Strategy<Value = Param>,
+  ) -> impl Strategy<Value = Instruction> {
+    (prop::sample::select(vec![5i64, 6]), condition, any::<Index>()).prop_map(
+      |(opcode, condition, target)| Instruction::Jump {
+        opcode,
+        condition,
+        target,
+      },
+    )
+  }
+
+  /// Instructions that cannot fail. Multiplications only take small
+  /// immediates, so a value can at most double with each instruction and
+  /// never overflows.
+  fn arb_valid_instruction() -> impl Strategy<Value = Instruction> {
+    let immediate = || arb_small_value().prop_map(Param::Immediate);
+    prop_oneof![
+      arb_binary(vec![1, 7, 8], arb_valid_param(), arb_valid_param()),
+      arb_binary(vec![2], immediate(), immediate()),
+      any::<Index>().prop_map(|target| Instruction::Input { target }),
+      arb_valid_param().prop_map(|value| Instruction::Output { value }),
+      arb_jump(arb_valid_param()),
+    ]
+  }
+
+  /// Instructions of every opcode, with parameters in any mode.
+  fn arb_instruction() -> impl Strategy<Value = Instruction> {
+    prop_oneof![
+      arb_binary(vec![1, 2, 7, 8], arb_param(), arb_param()),
+      any::<Index>().prop_map(|target| Instruction::Input { target }),
+      arb_param().prop_map(|value| Instruction::Output { value }),
+      arb_jump(arb_param()),
+      arb_param().prop_map(|offset| Instruction::AdjustBase { offset }),
+    ]
+  }
+
+  /// `i` as an Intcode value.
+  fn value_of(i: usize) -> i64 { i64::try_from(i).unwrap() }
+
+  /// Lays out `instructions`, a halt and the data cells as Intcode.
+  fn encode(instructions: &[Instruction], data: &[i64]) -> Vec<i64> {
+    let mut starts = vec![0];
+    for i in instructions {
+      let opcode = match i {
+        Instruction::Binary { opcode, .. }
+        | Instruction::Jump { opcode, .. } => *opcode,
+        Instruction::Input { .. } => 3,
+        Instruction::Output { .. } => 4,
+        Instruction::AdjustBase { .. } => 9,
+      };
+      starts.push(starts.last().unwrap() + instruction_length(opcode));
+    }
+    let code_len = starts.last().unwrap() + 1;
+    let len = code_len + data.len();
+    let mode = |p: &Param| match p {
+      Param::Position(_) => 0,
+      Param::Immediate(_) => 1,
+      Param::Relative(_) => 2,
+    };
+    let value = |p: &Param| match p {
+      Param::Position(i) | Param::Relative(i) => value_of(i.index(len)),
+      Param::Immediate(v) => *v,
+    };
+    let cell = |i: &Index| value_of(code_len + i.index(data.len()));
+
+    let mut program = vec![];
+    for (n, i) in instructions.iter().enumerate() {
+      match i {
+        Instruction::Binary { opcode, left, right, target } =>
+          program.extend(&[
+            opcode + 100 * mode(left) + 1000 * mode(right),
+            value(left),
+            value(right),
+            cell(target),
+          ]),
+        Instruction::Input { target } => program.extend(&[3, cell(target)]),
+        Instruction::Output { value: v } =>
+          program.extend(&[4 + 100 * mode(v), value(v)]),
+        Instruction::Jump { opcode, condition, target } => {
+          // Any later instruction start, the last one being the halt.
+          let later = &starts[n + 1..];
+          program.extend(&[
+            opcode + 100 * mode(condition) + 1000,
+            value(condition),
+            value_of(later[target.index(later.len())]),
+          ]);
+        },
+        Instruction::AdjustBase { offset } =>
+          program.extend(&[9 + 100 * mode(offset), value(offset)]),
+      }
+    }
+    program.push(99);
+    program.extend(data);
+    program
+  }
+
+  prop_compose! {
+    /// Generate valid programs, with enough input for every opcode 3. Every
+    /// address is inside the program and no value overflows, so they always
+    /// halt without an error.
+    fn arb_program()(
+      instructions in
+        prop::collection::vec(arb_valid_instruction(), 0..MAX_INSTRUCTIONS),
+      data in prop::collection::vec(arb_small_value(), DATA),
+      input in prop::collection::vec(arb_small_value(), MAX_INSTRUCTIONS),
+    ) -> (Vec<i64>, Vec<i64>) {
+      (encode(&instructions, &data), input)
+    }
+  }
+
+  prop_compose! {
+    /// Generate programs that may fail, with enough input for every opcode
+    /// 3. Relative parameters, opcode 9 and values of any size lead to
+    /// negative addresses and overflows.
+    fn arb_program_with_errors()(
+      instructions in
+        prop::collection::vec(arb_instruction(), 0..MAX_INSTRUCTIONS),
+      data in prop::collection::vec(arb_value(), DATA),
+      input in prop::collection::vec(arb_value(), MAX_INSTRUCTIONS),
+    ) -> (Vec<i64>, Vec<i64>) {
+      (encode(&instructions, &data), input)
+    }
+  }
+
+  /// `value` as an address. Only called on values that are not negative.
+  fn address_of(value: i64) -> usize { usize::try_from(value).unwrap() }
+
+  /// Reads parameter `n` (1-based) of the instruction at `ip`, with the
+  /// relative base `base`. Memory past the end reads as 0.
+  fn param(
+    memory: &[i64],
+    ip: usize,
+    base: i64,
+    n: u32,
+  ) -> Result<i64, IntcodeError> {
+    let p = memory[ip + n as usize];
+    let address = match memory[ip] / 10i64.pow(n + 1) % 10 {
+      0 => p,
+      1 => return Ok(p),
+      _ =>
+        base.checked_add(p).ok_or(IntcodeError::Overflow { position: ip })?,
+    };
+    if address < 0 {
+      return Err(IntcodeError::NegativeAddress { address, position: ip });
+    }
+    Ok(memory.get(address_of(address)).copied().unwrap_or(0))
+  }
+
+  /// A straightforward interpreter for the generated subset of Intcode.
+  /// Returns the final memory and the outputs, or the error that stopped
+  /// the program.
+  fn reference(
+    mut memory: Vec<i64>,
+    input: &[i64],
+  ) -> Result<(Vec<i64>, Vec<i64>), IntcodeError> {
+    let mut input = input.iter();
+    let mut output = vec![];
+    let mut ip = 0;
+    let mut base = 0i64;
+    loop {
+      let overflow = IntcodeError::Overflow { position: ip };
+      match memory[ip] % 100 {
+        opcode @ (1 | 2 | 7 | 8) => {
+          let a = param(&memory, ip, base, 1)?;
+          let b = param(&memory, ip, base, 2)?;
+          let target = address_of(memory[ip + 3]);
+          memory[target] = match opcode {
+            1 => a.checked_add(b).ok_or(overflow)?,
+            2 => a.checked_mul(b).ok_or(overflow)?,
+            7 => i64::from(a < b),
+            _ => i64::from(a == b),
+          };
+          ip += 4;
+        },
+        3 => {
+          let target = address_of(memory[ip + 1]);
+          memory[target] = *input.next().unwrap();
+          ip += 2;
+        },
+        4 => {
+          output.push(param(&memory, ip, base, 1)?);
+          ip += 2;
+        },
+        opcode @ (5 | 6) =>
+          if (param(&memory, ip, base, 1)? != 0) == (opcode == 5) {
+            ip = address_of(param(&memory, ip, base, 2)?);
+          } else {
+            ip += 3;
+          },
+        9 => {
+          base =
+            base.checked_add(param(&memory, ip, base, 1)?).ok_or(overflow)?;
+          ip += 2;
+        },
+        99 => return Ok((memory, output)),
+        opcode => panic!("Generated unknown opcode {}", opcode),
+      }
+    }
+  }
+
+  /// The program as a `Model` with its input queued.
+  fn model(program: &[i64], input: &[i64]) -> Model {
+    Model {
+      int_code: program.to_vec(),
+      input: input.iter().copied().collect(),
+      ..Model::default()
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn fuzz_pb_valid_program_halts((program, input) in arb_program()) {
+      let (memory, output) = reference(program.clone(), &input).unwrap();
+      let mut m = model(&program, &input);
+      prop_assert_eq!(m.run(), Ok(Status::Halted));
+      prop_assert_eq!(&m.int_code, &memory);
+      prop_assert_eq!(m.output.iter().copied().collect::<Vec<_>>(), output);
+    }
+
+    #[test]
+    fn fuzz_pb_run_matches_reference((program, input)
+                                       in arb_program_with_errors()) {
+      let mut m = model(&program, &input);
+      let result = m.run();
+      match reference(program.clone(), &input) {
+        Ok((memory, output)) => {
+          prop_assert_eq!(result, Ok(Status::Halted));
+          prop_assert_eq!(&m.int_code, &memory);
+          prop_assert_eq!(m.output.iter().copied().collect::<Vec<_>>(), output);
+          // Jumps only go forward, so no instruction runs twice.
+          prop_assert!(m.instruction_count <= MAX_INSTRUCTIONS as u64 + 1);
+        },
+        Err(e) => prop_assert_eq!(result, Err(e)),
+      }
+    }
+
+    #[test]
+    fn fuzz_pb_problem_2a_matches_reference((program, input) in arb_program()) {
+      let (memory, _) = reference(program.clone(), &input).unwrap();
+      prop_assert_eq!(problem_2a(model(&program, &input)).int_code, memory);
+    }
+
+    #[test]
+    fn fuzz_pb_backends_agree((program, input) in arb_program_with_errors()) {
+      let mut expected = model(&program, &input);
+      let result = expected.run();
+
+      let mut traced = model(&program, &input);
+      match trace::run(&mut traced) {
+        Ok(operations) =>
+          prop_assert_eq!(u64::try_from(operations.len()).unwrap(),
+                          traced.instruction_count),
+        Err(e) => prop_assert_eq!(Err(e), result.clone()),
+      }
+      prop_assert_eq!(&traced, &expected);
+
+      let options = Options { input: input.clone(), ..Options::default() };
+      let outcome = execute(model(&program, &[]), &options);
+      match &result {
+        Ok(_) => prop_assert_eq!(&outcome.stop, &Stop::Halted),
+        Err(e) => prop_assert_eq!(&outcome.stop, &Stop::Error(e.clone())),
+      }
+      prop_assert_eq!(&outcome.model, &expected);
+
+      let mut recorder = Recorder::new(model(&program, &input));
+      prop_assert_eq!(recorder.run(), result);
+      let (recorded, recording) = recorder.into_parts();
+      prop_assert_eq!(&recorded, &expected);
+      prop_assert_eq!(
+        recording.outputs().collect::<Vec<_>>(),
+        expected.output.iter().copied().collect::<Vec<_>>()
+      );
+    }
+  }
+}
@@ -0,0 +1,160 @@
+/// Tests for the full Intcode instruction set (parameter modes, I/O, jumps,
+/// comparisons and the relative base), based on the examples from later Advent
+/// of Code 2019 problems.
+#[cfg(test)]
+mod tests_aoc2019_2_intcode {
+  use aoc2019_2::*;
+
+  /// Runs `program` with the given inputs and returns all outputs.
+  fn run_with_input(program: &str, input: &[i64]) -> Vec<i64> {
+    let mut m: Model = program.parse().unwrap();
+    m.input.extend(input);
+    assert_eq!(m.run(), Ok(Status::Halted));
+    m.output.into_iter().collect()
+  }
+
+  #[test]
+  fn echo_input() {
+    assert_eq!(run_with_input("3,0,4,0,99", &[42]), vec![42]);
+  }
+
+  #[test]
+  fn parse_programs() {
+    let m: Model = " 1, -2,,3,\n".parse().unwrap();
+    assert_eq!(m.int_code, vec![1, -2, 3]);
+    for &(program, address, value) in &[
+      ("1-2", 0, "1-2"),
+      ("-", 0, "-"),
+      ("5,-,3", 1, "-"),
+      ("1,x", 1, "x"),
+      ("99,9223372036854775808", 1, "9223372036854775808"),
+    ] {
+      assert_eq!(
+        program.parse::<Model>(),
+        Err(ParseError { address, value: value.to_string() })
+      );
+    }
+    assert_eq!(
+      "5,-,3".parse::<Model>().unwrap_err().to_string(),
+      "bad value '-' at address 1"
+    );
+  }
+
+  #[test]
+  fn convert_programs() {
+    assert_eq!(Model::from(" 1, -2,,3,\n"), " 1, -2,,3,\n".parse().unwrap());
+    assert_eq!(Model::from(String::from("1,0,0,0,99")).int_code, vec![
+      1, 0, 0, 0, 99
+    ]);
+  }
+
+  #[test]
+  #[should_panic(
+    expected = "Not an Intcode program: bad value '-' at address 1"
+  )]
+  fn convert_unreadable_program() { let _ = Model::from("5,-,3"); }
+
+  #[test]
+  fn immediate_mode_and_negative_numbers() {
+    let m = problem_2a("1101,100,-1,4,0".parse().unwrap());
+    assert_eq!(m.get(4), 99);
+    assert!(m.halted);
+  }
+
+  #[test]
+  fn compare_equal_to_eight() {
+    let program = "3,9,8,9,10,9,4,9,99,-1,8";
+    assert_eq!(run_with_input(program, &[8]), vec![1]);
+    assert_eq!(run_with_input(program, &[7]), vec![0]);
+  }
+
+  #[test]
+  fn compare_less_than_eight_immediate() {
+    let program = "3,3,1107,-1,8,3,4,3,99";
+    assert_eq!(run_with_input(program, &[7]), vec![1]);
+    assert_eq!(run_with_input(program, &[9]), vec![0]);
+  }
+
+  #[test]
+  fn jump_if_zero() {
+    let program = "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9";
+    assert_eq!(run_with_input(program, &[0]), vec![0]);
+    assert_eq!(run_with_input(program, &[5]), vec![1]);
+  }
+
+  #[test]
+  fn relative_base_quine() {
+    let program = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+    let expected: Vec<i64> = program.parse::<Model>().unwrap().int_code;
+    assert_eq!(run_with_input(program, &[]), expected);
+  }
+
+  #[test]
+  fn large_numbers() {
+    assert_eq!(run_with_input("104,1125899906842624,99", &[]), vec![
+      1_125_899_906_842_624
+    ]);
+    assert_eq!(run_with_input("1102,34915192,34915192,7,4,7,99,0", &[]), vec![
+      1_219_070_632_396_864
+    ]);
+  }
+
+  #[test]
+  fn awaiting_input_then_resume() {
+    let mut m: Model = "3,0,4,0,99".parse().unwrap();
+    assert_eq!(m.run(), Ok(Status::AwaitingInput));
+    assert_eq!(m.position, 0);
+    assert_eq!(m.instruction_count, 0);
+    m.input.push_back(7);
+    assert_eq!(m.run(), Ok(Status::Halted));
+    assert_eq!(m.output, vec![7]);
+    assert_eq!(m.instruction_count, 3);
+  }
+
+  #[test]
+  fn step_after_halt() {
+    let mut m: Model = "99".parse().unwrap();
+    assert_eq!(m.step(), Ok(Status::Halted));
+    assert_eq!(m.step(), Err(IntcodeError::Halted));
+  }
+
+  #[test]
+  fn unknown_opcode() {
+    let mut m: Model = "1,0,0,0,42".parse().unwrap();
+    assert_eq!(
+      m.run(),
+      Err(IntcodeError::UnknownOpcode { opcode: 42, position: 4 })
+    );
+    assert_eq!(m.get(0), 2);
+  }
+
+  #[test]
+  fn immediate_write() {
+    let mut m: Model = "11101,1,1,0,99".parse().unwrap();
+    assert_eq!(m.step(), Err(IntcodeError::ImmediateWrite { position: 0 }));
+    assert_eq!(m.position, 0);
+  }
+
+  #[test]
+  fn address_too_large() {
+    let mut m: Model = "1101,0,0,9000000000000000000,99".parse().unwrap();
+    let before = m.clone();
+    assert_eq!(
+      m.step(),
+      Err(IntcodeError::AddressTooLarge {
+        address: 9_000_000_000_000_000_000,
+        position: 0
+      })
+    );
+    assert_eq!(m, before);
+
+    let mut m: Model =
+      format!("1101,0,7,{},99", MEMORY_LIMIT - 1).parse().unwrap();
+    assert_eq!(m.run(), Ok(Status::Halted));
+    assert_eq!(m.get(MEMORY_LIMIT - 1), 7);
+  }
+
+  #[test]
+  #[should_panic(expected = "Bad input string, unknown opcode 42")]
+  fn problem_2a_panics_on_error() { let _ = problem_2a("42".parse().unwrap()); }
+}
@@ -0,0 +1,103 @@
+/// Tests for recording a session's I/O and replaying it against a program.
+#[cfg(test)]
+mod tests_aoc2019_2_replay {
+  use aoc2019_2::replay::*;
+  use aoc2019_2::*;
+
+  /// Reads a value, writes it back out and jumps to the start, forever.
+  const ECHO: &str = "3,7,4,7,1105,1,0,0";
+
+  /// Like `ECHO`, but doubles each value before writing it out.
+  const DOUBLE: &str = "3,11,1002,11,2,11,4,11,1105,1,0,0";
+
+  /// An interactive session with `ECHO`: two values typed in one at a time.
+  fn echo_session() -> Recording {
+    let mut r = Recorder::new(ECHO.parse().unwrap());
+    assert_eq!(r.run(), Ok(Status::AwaitingInput));
+    r.push_input(1);
+    assert_eq!(r.run(), Ok(Status::AwaitingInput));
+    r.push_input(-2);
+    assert_eq!(r.run(), Ok(Status::AwaitingInput));
+    r.into_parts().1
+  }
+
+  #[test]
+  fn record_events_with_steps() {
+    assert_eq!(echo_session().events, vec![
+      Event::Input { step: 0, value: 1 },
+      Event::Output { step: 1, value: 1 },
+      Event::Input { step: 3, value: -2 },
+      Event::Output { step: 4, value: -2 },
+    ]);
+  }
+
+  #[test]
+  fn record_inputs_and_outputs() {
+    let recording = echo_session();
+    assert_eq!(recording.inputs().collect::<Vec<_>>(), vec![1, -2]);
+    assert_eq!(recording.outputs().collect::<Vec<_>>(), vec![1, -2]);
+  }
+
+  #[test]
+  fn replay_matches() {
+    let m = replay(ECHO.parse().unwrap(), &echo_session()).unwrap();
+    assert_eq!(m.output, vec![1, -2]);
+    assert!(m.input.is_empty());
+  }
+
+  #[test]
+  fn replay_from_json() {
+    let recording = Recording::from_json(&echo_session().to_json()).unwrap();
+    assert_eq!(recording, echo_session());
+    assert!(replay(ECHO.parse().unwrap(), &recording).is_ok());
+  }
+
+  #[test]
+  fn replay_diverges_on_different_output() {
+    assert_eq!(
+      replay(DOUBLE.parse().unwrap(), &echo_session()),
+      Err(ReplayError::Diverged {
+        step: 2,
+        expected: Some(Event::Output { step: 1, value: 1 }),
+        actual: Some(Event::Output { step: 2, value: 2 }),
+      })
+    );
+  }
+
+  #[test]
+  fn replay_diverges_on_early_halt() {
+    assert_eq!(
+      replay("3,5,4,5,99,0".parse().unwrap(), &echo_session()),
+      Err(ReplayError::Diverged {
+        step: 3,
+        expected: Some(Event::Input { step: 3, value: -2 }),
+        actual: None,
+      })
+    );
+  }
+
+  #[test]
+  fn replay_diverges_on_extra_output() {
+    let recording =
+      Recording { events: vec![Event::Output { step: 0, value: 5 }] };
+    assert_eq!(
+      replay("104,5,104,6,99".parse().unwrap(), &recording),
+      Err(ReplayError::Diverged {
+        step: 1,
+        expected: None,
+        actual: Some(Event::Output { step: 1, value: 6 }),
+      })
+    );
+  }
+
+  #[test]
+  fn replay_reports_machine_errors() {
+    assert_eq!(
+      replay("3,3,0,0".parse().unwrap(), &echo_session()),
+      Err(ReplayError::Machine {
+        step: 1,
+        error: IntcodeError::UnknownOpcode { opcode: 0, position: 2 }
+      })
+    );
+  }
+}
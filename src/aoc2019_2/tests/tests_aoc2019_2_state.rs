@@ -0,0 +1,147 @@
+/// Tests for saving and loading machine states in both formats.
+#[cfg(test)]
+mod tests_aoc2019_2_state {
+  use aoc2019_2::state::*;
+  use aoc2019_2::*;
+  use proptest::prelude::*;
+
+  /// A machine part-way through an echo loop, with pending input and output.
+  fn paused_model() -> Model {
+    let mut m: Model = "3,11,4,11,109,-3,1105,1,0,99,0,0".parse().unwrap();
+    m.input.extend(&[5, -6, 7]);
+    m.step().unwrap();
+    m.step().unwrap();
+    m.step().unwrap();
+    m
+  }
+
+  prop_compose! {
+    /// Generate `Model` values with arbitrary state.
+    fn arb_model()
+                (int_code in prop::collection::vec(any::<i64>(), 0..50),
+                 position in any::<usize>(),
+                 relative_base in any::<i64>(),
+                 input in prop::collection::vec(any::<i64>(), 0..10),
+                 output in prop::collection::vec(any::<i64>(), 0..10),
+                 halted in any::<bool>(),
+                 instruction_count in any::<u64>())
+                -> Model {
+      Model {
+        int_code,
+        position,
+        relative_base,
+        input: input.into(),
+        output: output.into(),
+        halted,
+        instruction_count,
+      }
+    }
+  }
+
+  #[test]
+  fn json_round_trip() {
+    let m = paused_model();
+    assert_eq!(from_json(&to_json(&m)).unwrap(), m);
+  }
+
+  #[test]
+  fn binary_round_trip() {
+    let m = paused_model();
+    assert_eq!(from_binary(&to_binary(&m)).unwrap(), m);
+  }
+
+  #[test]
+  fn binary_is_smaller_than_json() {
+    let m: Model =
+      std::fs::read_to_string("src/input_2a").unwrap().parse().unwrap();
+    assert!(to_binary(&m).len() < to_json(&m).len());
+  }
+
+  #[test]
+  fn resume_after_load() {
+    let mut original = paused_model();
+    let mut restored =
+      from_bytes(&to_bytes(&original, Format::Binary)).unwrap();
+    for _ in 0..4 {
+      assert_eq!(restored.step(), original.step());
+    }
+    assert_eq!(restored, original);
+    assert_eq!(restored.output, vec![5, -6]);
+  }
+
+  #[test]
+  fn save_and_load_file() {
+    let path = std::env::temp_dir().join("tests_aoc2019_2_state.icms");
+    let m = paused_model();
+    for format in &[Format::Json, Format::Binary] {
+      save(&m, &path, *format).unwrap();
+      assert_eq!(load(&path).unwrap(), m);
+    }
+    std::fs::remove_file(&path).unwrap();
+  }
+
+  #[test]
+  fn json_fields() {
+    let json = to_json(&"99".parse().unwrap());
+    assert_eq!(
+      json,
+      "{\"version\":1,\"memory\":[99],\"position\":0,\"relative_base\":0,\"\
+       instruction_count\":0,\"input\":[],\"output\":[],\"halted\":false}"
+    );
+  }
+
+  #[test]
+  fn json_unsupported_version() {
+    match from_json("{\"version\":2,\"registers\":[]}") {
+      Err(StateError::UnsupportedVersion(2)) => (),
+      other => panic!("Unexpected result {:?}", other),
+    }
+  }
+
+  #[test]
+  fn binary_unsupported_version() {
+    let mut bytes = to_binary(&"99".parse().unwrap());
+    bytes[MAGIC.len()] = 9;
+    match from_binary(&bytes) {
+      Err(StateError::UnsupportedVersion(9)) => (),
+      other => panic!("Unexpected result {:?}", other),
+    }
+  }
+
+  #[test]
+  fn binary_bad_magic() {
+    match from_binary(b"NOPE\x01") {
+      Err(StateError::BadMagic) => (),
+      other => panic!("Unexpected result {:?}", other),
+    }
+  }
+
+  #[test]
+  fn binary_trailing_bytes() {
+    let mut bytes = to_binary(&"99".parse().unwrap());
+    bytes.push(0);
+    match from_binary(&bytes) {
+      Err(StateError::TrailingBytes) => (),
+      other => panic!("Unexpected result {:?}", other),
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn state_pb_json_round_trip(m in arb_model()) {
+      prop_assert_eq!(from_json(&to_json(&m)).unwrap(), m);
+    }
+
+    #[test]
+    fn state_pb_binary_round_trip(m in arb_model()) {
+      prop_assert_eq!(from_binary(&to_binary(&m)).unwrap(), m);
+    }
+
+    #[test]
+    fn state_pb_binary_truncated(m in arb_model(), cut in any::<usize>()) {
+      let bytes = to_binary(&m);
+      let cut = cut % bytes.len();
+      prop_assert!(from_binary(&bytes[..cut]).is_err());
+    }
+  }
+}
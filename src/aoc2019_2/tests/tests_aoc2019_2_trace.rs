@@ -0,0 +1,75 @@
+/// Tests for the operation trace used by the visualisers.
+#[cfg(test)]
+mod tests_aoc2019_2_trace {
+  use aoc2019_2::trace::*;
+  use aoc2019_2::*;
+
+  #[test]
+  fn trace_sample_4() {
+    let mut m: Model = "1,1,1,4,99,5,6,0,99".parse().unwrap();
+    let operations = run(&mut m).unwrap();
+    let history: Vec<String> =
+      operations.iter().map(ToString::to_string).collect();
+    assert_eq!(history, vec![
+      "Placing 2 (OpCode 1 [1, 1]) into position 4",
+      "Placing 30 (OpCode 2 [5, 6]) into position 0",
+      "Program has ended based on code 99 at position 8",
+    ]);
+    assert_eq!(operations[1].write, Some((0, 30)));
+    assert_eq!(operations[1].step, 1);
+    assert_eq!(operations[1].next_position, 8);
+  }
+
+  #[test]
+  fn trace_matches_model_step() {
+    let program = "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9";
+    let mut traced: Model = program.parse().unwrap();
+    let mut plain: Model = program.parse().unwrap();
+    traced.input.push_back(3);
+    plain.input.push_back(3);
+    loop {
+      let operation = step(&mut traced).unwrap();
+      assert_eq!(plain.step(), Ok(operation.status));
+      assert_eq!(traced, plain);
+      if operation.status == Status::Halted {
+        break;
+      }
+    }
+  }
+
+  #[test]
+  fn trace_io_and_jumps() {
+    let mut m: Model = "3,11,1005,11,7,104,0,104,1,99,0,0".parse().unwrap();
+    assert_eq!(
+      step(&mut m).unwrap().to_string(),
+      "Waiting for input at position 0"
+    );
+    m.input.push_back(5);
+    let history: Vec<String> =
+      run(&mut m).unwrap().iter().map(ToString::to_string).collect();
+    assert_eq!(history, vec![
+      "Placing 5 (OpCode 3 input) into position 11",
+      "Moved to position 7 (OpCode 5 [5, 7])",
+      "Output 1 (OpCode 4)",
+      "Program has ended based on code 99 at position 9",
+    ]);
+  }
+
+  #[test]
+  fn trace_relative_base() {
+    let mut m: Model = "109,19,204,-15,99".parse().unwrap();
+    let operations = run(&mut m).unwrap();
+    assert_eq!(
+      operations[0].to_string(),
+      "Moved relative base to 19 (OpCode 9 [19])"
+    );
+    assert_eq!(operations[1].to_string(), "Output 99 (OpCode 4)");
+  }
+
+  #[test]
+  fn trace_after_halt() {
+    let mut m: Model = "99".parse().unwrap();
+    step(&mut m).unwrap();
+    assert_eq!(step(&mut m), Err(IntcodeError::Halted));
+  }
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "web")]
+
+/// Tests for the browser visualiser's update logic, which runs without a
+/// browser.
+#[cfg(test)]
+mod tests_aoc2019_2_web {
+  use aoc2019_2::Model;
+  use aoc2019_2::web::*;
+
+  /// Handles `msg` and every message that follows from it, ignoring delays.
+  fn dispatch(web: &mut WebModel, msg: Msg) {
+    let mut next = Some(msg);
+    while let Some(msg) = next.take() {
+      next = match update(msg, web) {
+        Some(Order::Now(msg) | Order::After(msg, _)) => Some(msg),
+        None => None,
+      };
+    }
+  }
+
+  #[test]
+  fn process_sample_4() {
+    let mut web = WebModel::default();
+    dispatch(&mut web, Msg::InputChanged("1,1,1,4,99,5,6,0,99\n".into()));
+    assert_eq!(web.ui_model.input, "1,1,1,4,99,5,6,0,99");
+
+    dispatch(&mut web, Msg::ProcessInputClicked);
+    assert_eq!(web.model.get(0), 30);
+    assert!(!web.ui_model.process_button_disabled);
+    assert_eq!(
+      web.ui_model.operations_history.lines().collect::<Vec<_>>(),
+      vec![
+        "Initializing Logic",
+        "Placing 2 (OpCode 1 [1, 1]) into position 4",
+        "Placing 30 (OpCode 2 [5, 6]) into position 0",
+        "Program has ended based on code 99 at position 8",
+      ]
+    );
+  }
+
+  #[test]
+  fn process_newer_opcodes() {
+    let mut web = WebModel::default();
+    dispatch(&mut web, Msg::InputChanged("1101,100,-1,4,0".into()));
+    dispatch(&mut web, Msg::ProcessInputClicked);
+    assert_eq!(web.model.get(4), 99);
+    assert!(web.model.halted);
+  }
+
+  #[test]
+  fn process_unknown_opcode() {
+    let mut web = WebModel::default();
+    dispatch(&mut web, Msg::InputChanged("42".into()));
+    dispatch(&mut web, Msg::ProcessInputClicked);
+    assert!(
+      web
+        .ui_model
+        .operations_history
+        .ends_with("Error, unknown opcode 42 at position 0\n")
+    );
+    assert!(!web.ui_model.process_button_disabled);
+  }
+
+  #[test]
+  fn process_unreadable_program() {
+    let mut web = WebModel::default();
+    dispatch(&mut web, Msg::InputChanged("1-2,99".into()));
+    dispatch(&mut web, Msg::ProcessInputClicked);
+    assert_eq!(
+      web.ui_model.operations_history,
+      "Error, bad value '1-2' at address 0\n"
+    );
+    assert_eq!(web.model, Model::default());
+    assert!(!web.ui_model.process_button_disabled);
+  }
+
+  #[test]
+  fn delay_is_used_between_steps() {
+    let mut web = WebModel::default();
+    update(Msg::DelayChanged("250".into()), &mut web);
+    update(Msg::DelayChanged("abc".into()), &mut web);
+    assert_eq!(web.ui_model.visual_delay_ms, 250);
+    update(Msg::InputChanged("99".into()), &mut web);
+    assert_eq!(
+      update(Msg::LogicInitialize, &mut web),
+      Some(Order::After(Msg::LogicProcessCurrentOpCode, 250))
+    );
+  }
+
+  #[test]
+  fn memory_highlights_current_instruction() {
+    let m = "1,0,0,0,99".parse().unwrap();
+    let html = view_memory(&m);
+    assert_eq!(html.matches("<strong>").count(), 4);
+  }
+}
@@ -0,0 +1,69 @@
+#![feature(test)]
+
+extern crate test;
+
+use aoc2019_3::index::SectionIndex;
+use aoc2019_3::problem_3a::MovementDirection::{D, L, R, U};
+use aoc2019_3::problem_3a::*;
+use aoc2019_3::sweep;
+use std::convert::TryFrom;
+use test::Bencher;
+
+/// A deterministic wire with `segments` sections, wandering around the
+/// central port so that it crosses other wires built the same way. Uses a
+/// linear congruential generator, so runs are comparable.
+fn wire(segments: usize, seed: u64) -> Wire {
+  let mut state = seed;
+  let moves = (0..segments)
+    .map(|i| {
+      state = state
+        .wrapping_mul(6_364_136_223_846_793_005)
+        .wrapping_add(1_442_695_040_888_963_407);
+      let distance = 1 + ((state >> 33) % 200) as i32;
+      // Alternate between horizontal and vertical moves, as real wires do.
+      let direction = match (i % 2, (state >> 32) & 1) {
+        (0, 0) => R,
+        (0, _) => L,
+        (_, 0) => U,
+        _ => D,
+      };
+      PathEntry::new(direction, distance)
+    })
+    .collect();
+  Wire::try_from(&Path::new_internal(moves)).unwrap()
+}
+
+#[bench]
+fn bench_pairwise_2_000(b: &mut Bencher) {
+  let (wire1, wire2) = (wire(2_000, 1), wire(2_000, 2));
+  b.iter(|| wire1.crossings(&wire2));
+}
+
+#[bench]
+fn bench_sweep_2_000(b: &mut Bencher) {
+  let (wire1, wire2) = (wire(2_000, 1), wire(2_000, 2));
+  b.iter(|| sweep::crossings(&wire1, &wire2));
+}
+
+#[bench]
+fn bench_sweep_50_000(b: &mut Bencher) {
+  let (wire1, wire2) = (wire(50_000, 1), wire(50_000, 2));
+  b.iter(|| sweep::crossings(&wire1, &wire2));
+}
+
+#[bench]
+fn bench_steps_to_linear_2_000(b: &mut Bencher) {
+  let (wire1, wire2) = (wire(2_000, 1), wire(2_000, 2));
+  b.iter(|| {
+    wire2.get_corners().iter().filter_map(|p| wire1.steps_to(p)).count()
+  });
+}
+
+#[bench]
+fn bench_steps_to_index_2_000(b: &mut Bencher) {
+  let (wire1, wire2) = (wire(2_000, 1), wire(2_000, 2));
+  b.iter(|| {
+    let index = SectionIndex::new(&wire1);
+    wire2.get_corners().iter().filter_map(|p| index.steps_to(p)).count()
+  });
+}
@@ -0,0 +1,276 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use crate::index::{line_through, point_on};
+use crate::problem_3a::{
+  Frame, Intersection, Orientation, Overlap, Path, Point, Wire, WireSection,
+  parse_paths,
+};
+use crate::{route, sweep};
+
+/// Any number of wires on the same grid, numbered from 0 in the order they
+/// were given.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Board {
+  wires: Vec<Wire>,
+}
+
+impl Board {
+  #[must_use]
+  pub const fn new(wires: Vec<Wire>) -> Self { Self { wires } }
+
+  #[must_use]
+  pub fn get_wires(&self) -> &[Wire] { &self.wires }
+
+  /// The smallest rectangle holding every corner of every wire and the
+  /// central port, as `(min_x, min_y, max_x, max_y)`.
+  #[must_use]
+  pub fn bounding_box(&self) -> (i32, i32, i32, i32) {
+    self.wires.iter().filter_map(Wire::bounding_box).fold(
+      (0, 0, 0, 0),
+      |(min_x, min_y, max_x, max_y), (x1, y1, x2, y2)| {
+        (min_x.min(x1), min_y.min(y1), max_x.max(x2), max_y.max(y2))
+      },
+    )
+  }
+
+  /// The shortest route from `start` to `goal` that never crosses or
+  /// touches any of the wires, as `route::route` finds it.
+  #[must_use]
+  pub fn route(&self, start: Point, goal: Point) -> Option<Path> {
+    route::route(&self.wires, start, goal)
+  }
+
+  /// Every place where two wires meet, with the numbers of all the wires
+  /// that meet there in the same way: each point where two wires cross or
+  /// touch, and each segment along which two wires run together, however
+  /// long it is. The central port, where every wire starts, is left out as a
+  /// point of its own, but not from the overlaps that run through it.
+  ///
+  /// Points and overlaps found for different pairs of wires may lie on each
+  /// other; `Board::meeting_points` sorts them out.
+  #[must_use]
+  pub fn crossings(&self) -> BTreeMap<Intersection, BTreeSet<usize>> {
+    let mut crossings: BTreeMap<Intersection, BTreeSet<usize>> =
+      BTreeMap::new();
+    for (i, wire1) in self.wires.iter().enumerate() {
+      for (j, wire2) in self.wires.iter().enumerate().skip(i + 1) {
+        for intersection in sweep::crossings(wire1, wire2) {
+          if intersection != Intersection::Point(Point::ORIGIN) {
+            crossings.entry(intersection).or_default().extend(&[i, j]);
+          }
+        }
+      }
+    }
+    crossings
+  }
+
+  /// The places where at least `k` wires meet, with the numbers of the wires
+  /// that meet there, ordered by where they start. Where the same wires run
+  /// together, they are listed once as an `Intersection::Overlap`, which is
+  /// split wherever another wire joins, leaves or crosses it, so that every
+  /// point is listed once. The central port is left out.
+  ///
+  /// This works from the ends of the overlaps and the points where wires
+  /// cross, so the time it takes does not depend on how long the overlaps
+  /// are.
+  #[must_use]
+  pub fn meeting_points(&self, k: usize) -> Vec<(Intersection, Vec<usize>)> {
+    let crossings = self.crossings();
+    let (lines, mut points) = lines_and_points(&crossings);
+
+    // The positions along each line where the wires there may change.
+    let mut stops: BTreeMap<&Line, BTreeSet<i32>> = BTreeMap::new();
+    for (point, wires) in &mut points {
+      for orientation in &ORIENTATIONS {
+        let (line, along) = line_through(orientation, *point);
+        if let Some((line, spans)) = lines.get_key_value(&line) {
+          stops.entry(line).or_default().insert(along);
+          for (_, _, shared) in spans
+            .iter()
+            .filter(|(start, end, _)| (*start..=*end).contains(&along))
+          {
+            wires.extend(shared.iter());
+          }
+        }
+      }
+    }
+    if let Some(wires) = points.get_mut(&Point::ORIGIN) {
+      wires.clear();
+    }
+
+    let enough =
+      |wires: &BTreeSet<usize>| !wires.is_empty() && wires.len() >= k;
+    let mut meetings: BTreeMap<Intersection, BTreeSet<usize>> = points
+      .iter()
+      .filter(|(_, wires)| enough(wires))
+      .map(|(point, wires)| (Intersection::Point(*point), wires.clone()))
+      .collect();
+    let mut on_overlaps = BTreeSet::new();
+    for (line, spans) in &lines {
+      for (start, end, wires) in runs(line, spans, &stops[line], &points) {
+        if !enough(&wires) {
+          continue;
+        }
+        // A point may be where the same wires turn from one line to
+        // another, so leave out the ones already on an overlap.
+        let mut from = start;
+        let taken = stops[line]
+          .range(start..=end)
+          .copied()
+          .filter(|&along| on_overlaps.contains(&point_on(line, along)))
+          .collect::<Vec<_>>();
+        for (to, next) in taken
+          .iter()
+          .map(|&along| (along - 1, along + 1))
+          .chain(std::iter::once((end, end)))
+        {
+          if from < to {
+            let overlap =
+              Overlap::new(point_on(line, from), point_on(line, to));
+            on_overlaps.extend(
+              stops[line].range(from..=to).map(|&along| point_on(line, along)),
+            );
+            meetings.insert(Intersection::Overlap(overlap), wires.clone());
+          } else if from == to {
+            meetings
+              .insert(Intersection::Point(point_on(line, from)), wires.clone());
+          }
+          from = next;
+        }
+      }
+    }
+
+    let mut meetings: Vec<(Intersection, Vec<usize>)> = meetings
+      .into_iter()
+      .filter(|(intersection, _)| match intersection {
+        Intersection::Point(point) => !on_overlaps.contains(point),
+        Intersection::Overlap(_) => true,
+      })
+      .map(|(intersection, wires)| (intersection, wires.into_iter().collect()))
+      .collect();
+    meetings.sort_by_key(|(intersection, _)| {
+      (start(intersection), intersection.clone())
+    });
+    meetings
+  }
+
+  /// For every pair of wires, their crossing closest to the origin of
+  /// `frame`, or `None` if they do not cross. The matrix is symmetric, and
+  /// `None` on the diagonal.
+  #[must_use]
+  pub fn closest_crossing_matrix(
+    &self,
+    frame: Frame,
+  ) -> Vec<Vec<Option<Point>>> {
+    let n = self.wires.len();
+    let mut matrix = vec![vec![None; n]; n];
+    for (i, wire1) in self.wires.iter().enumerate() {
+      for (j, wire2) in self.wires.iter().enumerate().skip(i + 1) {
+        let closest = frame.closest_crossing(wire1, wire2);
+        matrix[i][j] = closest;
+        matrix[j][i] = closest;
+      }
+    }
+    matrix
+  }
+}
+
+/// A straight line of the grid, as `index::line_through` gives it.
+type Line = (Orientation, i64);
+
+/// Where the overlaps on one line start and end along it, with the wires
+/// that run together along each.
+type Spans<'a> = Vec<(i32, i32, &'a BTreeSet<usize>)>;
+
+const ORIENTATIONS: [Orientation; 4] = [
+  Orientation::Horizontal,
+  Orientation::Vertical,
+  Orientation::Diagonal,
+  Orientation::AntiDiagonal,
+];
+
+/// The overlaps among `crossings` grouped by the line they lie on, and the
+/// points where the wires may change along those lines: the points where
+/// wires cross, with the wires that cross there, the ends of the overlaps and
+/// the central port.
+fn lines_and_points(
+  crossings: &BTreeMap<Intersection, BTreeSet<usize>>,
+) -> (BTreeMap<Line, Spans<'_>>, BTreeMap<Point, BTreeSet<usize>>) {
+  let mut lines: BTreeMap<Line, Spans> = BTreeMap::new();
+  let mut points: BTreeMap<Point, BTreeSet<usize>> = BTreeMap::new();
+  points.insert(Point::ORIGIN, BTreeSet::new());
+  for (intersection, wires) in crossings {
+    match intersection {
+      Intersection::Point(point) =>
+        points.entry(*point).or_default().extend(wires.iter()),
+      Intersection::Overlap(overlap) => {
+        let (start, end) = (overlap.get_start(), overlap.get_end());
+        let orientation = WireSection::create(start, end).get_orientation();
+        let (line, from) = line_through(&orientation, start);
+        let (_, to) = line_through(&orientation, end);
+        lines.entry(line).or_default().push((from, to, wires));
+        points.entry(start).or_default();
+        points.entry(end).or_default();
+      },
+    }
+  }
+  (lines, points)
+}
+
+/// The stretches of `line` along which the same wires meet, as where each
+/// starts and ends along the line and the wires that meet there. At each of
+/// the `stops`, the wires are the ones in `points`; between two of them, they
+/// are the wires of every span that covers both.
+fn runs(
+  line: &Line,
+  spans: &[(i32, i32, &BTreeSet<usize>)],
+  stops: &BTreeSet<i32>,
+  points: &BTreeMap<Point, BTreeSet<usize>>,
+) -> Vec<(i32, i32, BTreeSet<usize>)> {
+  let mut pieces = vec![];
+  let mut stops = stops.iter().copied().peekable();
+  while let Some(stop) = stops.next() {
+    pieces.push((stop, stop, points[&point_on(line, stop)].clone()));
+    if let Some(&next) = stops.peek() {
+      if next - stop > 1 {
+        let wires = spans
+          .iter()
+          .filter(|(start, end, _)| *start <= stop && next <= *end)
+          .flat_map(|(_, _, wires)| wires.iter().copied())
+          .collect();
+        pieces.push((stop + 1, next - 1, wires));
+      }
+    }
+  }
+
+  let mut runs: Vec<(i32, i32, BTreeSet<usize>)> = vec![];
+  for (start, end, wires) in pieces {
+    match runs.last_mut() {
+      Some(run) if run.2 == wires => run.1 = end,
+      _ => runs.push((start, end, wires)),
+    }
+  }
+  runs
+}
+
+/// Where `intersection` starts.
+const fn start(intersection: &Intersection) -> Point {
+  match intersection {
+    Intersection::Point(point) => *point,
+    Intersection::Overlap(overlap) => overlap.get_start(),
+  }
+}
+
+impl FromStr for Board {
+  type Err = String;
+
+  /// Parses one `Path` per non-empty line, reporting errors as
+  /// `parse_paths` does.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let wires =
+      parse_paths(s)?.iter().map(Wire::try_from).collect::<Result<_, _>>()?;
+    Ok(Self::new(wires))
+  }
+}
@@ -0,0 +1,131 @@
+use crate::board::Board;
+use crate::problem_3a::{Orientation, Point};
+
+/// What a cell of the diagram holds so far.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Cell {
+  Empty,
+  /// Wire number `wire` passes through, drawn as `symbol`.
+  Wire {
+    wire: usize,
+    symbol: char,
+  },
+  /// Two or more wires meet.
+  Crossing,
+}
+
+impl Cell {
+  /// The cell after wire number `wire` passes through it as `symbol`. A wire
+  /// that turns or crosses itself is drawn as `+`.
+  const fn add(self, wire: usize, symbol: char) -> Self {
+    match self {
+      Self::Empty => Self::Wire { wire, symbol },
+      Self::Wire { wire: w, symbol: s } if w == wire =>
+        Self::Wire { wire, symbol: if s == symbol { s } else { '+' } },
+      Self::Wire { .. } | Self::Crossing => Self::Crossing,
+    }
+  }
+
+  const fn symbol(self) -> char {
+    match self {
+      Self::Empty => '.',
+      Self::Wire { symbol, .. } => symbol,
+      Self::Crossing => 'X',
+    }
+  }
+}
+
+/// Draw the cells of `board` from `top_left` to `bottom_right`, both
+/// included, one row per line.
+fn render_area(board: &Board, top_left: Point, bottom_right: Point) -> String {
+  let (min_x, min_y) = (top_left.get_x(), top_left.get_y());
+  let (max_x, max_y) = (bottom_right.get_x(), bottom_right.get_y());
+  let columns = (min_x..=max_x).count();
+  let mut grid = vec![vec![Cell::Empty; columns]; (min_y..=max_y).count()];
+  let index = |from: i32, to: i32| (from..to).count();
+
+  for (i, wire) in board.get_wires().iter().enumerate() {
+    for section in wire.get_sections() {
+      let (start, end) = (section.get_start(), section.get_end());
+      // Only the part of the section inside the area is drawn, so that
+      // cropping a large board stays cheap.
+      match section.get_orientation() {
+        Orientation::Horizontal if (min_y..=max_y).contains(&start.get_y()) => {
+          let row = &mut grid[index(min_y, start.get_y())];
+          for x in start.get_x().max(min_x)..=end.get_x().min(max_x) {
+            let cell = &mut row[index(min_x, x)];
+            *cell = cell.add(i, '-');
+          }
+        },
+        Orientation::Vertical if (min_x..=max_x).contains(&start.get_x()) => {
+          let column = index(min_x, start.get_x());
+          for y in start.get_y().max(min_y)..=end.get_y().min(max_y) {
+            let cell = &mut grid[index(min_y, y)][column];
+            *cell = cell.add(i, '|');
+          }
+        },
+        Orientation::Diagonal | Orientation::AntiDiagonal => {
+          let (symbol, slope) =
+            if section.get_orientation() == Orientation::Diagonal {
+              ('\\', 1)
+            } else {
+              ('/', -1)
+            };
+          for x in start.get_x().max(min_x)..=end.get_x().min(max_x) {
+            let y = start.get_y() + slope * (x - start.get_x());
+            if (min_y..=max_y).contains(&y) {
+              let cell = &mut grid[index(min_y, y)][index(min_x, x)];
+              *cell = cell.add(i, symbol);
+            }
+          }
+        },
+        _ => (),
+      }
+    }
+  }
+
+  let mut diagram = String::new();
+  for (y, row) in (min_y..=max_y).zip(&grid) {
+    for (x, cell) in (min_x..=max_x).zip(row) {
+      diagram.push(if Point::new(x, y) == Point::ORIGIN {
+        'o'
+      } else {
+        cell.symbol()
+      });
+    }
+    diagram.push('\n');
+  }
+  diagram
+}
+
+/// Draw `board` as text, in the style of the diagrams in the puzzle
+/// statement.
+///
+/// Sections are drawn with `-`, `|`, `\` and `/`, with `+` where a wire
+/// turns or crosses itself, `X` where different wires meet, `o` for the
+/// central port and `.` for empty cells. The diagram covers the bounding box of
+/// the board with a border of one empty cell, and `y` increases down the page.
+///
+/// The diagram has one character per cell, so this is only suitable for small
+/// boards. Use `render_around` to see part of a large one.
+#[must_use]
+pub fn render(board: &Board) -> String {
+  let (min_x, min_y, max_x, max_y) = board.bounding_box();
+  render_area(
+    board,
+    Point::new(min_x.saturating_sub(1), min_y.saturating_sub(1)),
+    Point::new(max_x.saturating_add(1), max_y.saturating_add(1)),
+  )
+}
+
+/// Draw the square of `board` within `radius` cells of `centre` in either
+/// direction, like `render`. The square stops at the edges of the grid.
+#[must_use]
+pub fn render_around(board: &Board, centre: Point, radius: i32) -> String {
+  let (x, y) = (centre.get_x(), centre.get_y());
+  render_area(
+    board,
+    Point::new(x.saturating_sub(radius), y.saturating_sub(radius)),
+    Point::new(x.saturating_add(radius), y.saturating_add(radius)),
+  )
+}
@@ -0,0 +1,95 @@
+use crate::board::Board;
+use crate::problem_3a::{Intersection, Overlap, Point, Wire};
+
+/// A `GeoJSON` position for `point`, as `[x, y]` with `y` negated, so that up
+/// on the grid is up in a viewer, which draws `y` increasing upwards like a
+/// latitude.
+fn position(point: Point) -> String {
+  format!("[{},{}]", point.get_x(), -i64::from(point.get_y()))
+}
+
+/// The positions of `points`, as a `GeoJSON` array.
+fn positions(points: &[Point]) -> String {
+  let points: Vec<String> = points.iter().map(|&p| position(p)).collect();
+  format!("[{}]", points.join(","))
+}
+
+/// A `GeoJSON` feature with the given geometry and properties, each already
+/// written as JSON.
+fn feature(geometry: &str, properties: &str) -> String {
+  format!(
+    "{{\"type\":\"Feature\",\"geometry\":{geometry},\"properties\":\
+     {properties}}}"
+  )
+}
+
+/// A `GeoJSON` `LineString` geometry through the corners of `wire`, in the
+/// order they are visited, or `None` if the wire has no corners at all.
+///
+/// A line string needs at least two positions, so a wire that never leaves
+/// its start repeats it.
+#[must_use]
+pub fn line_string(wire: &Wire) -> Option<String> {
+  let coordinates = match wire.get_corners() {
+    [] => return None,
+    &[corner] => positions(&[corner, corner]),
+    corners => positions(corners),
+  };
+  Some(format!("{{\"type\":\"LineString\",\"coordinates\":{coordinates}}}"))
+}
+
+/// A `GeoJSON` `MultiPoint` geometry of `points`, such as crossings.
+#[must_use]
+pub fn multi_point(points: &[Point]) -> String {
+  format!("{{\"type\":\"MultiPoint\",\"coordinates\":{}}}", positions(points))
+}
+
+/// A `GeoJSON` `MultiLineString` geometry of `overlaps`, each from its start
+/// to its end.
+#[must_use]
+pub fn multi_line_string(overlaps: &[Overlap]) -> String {
+  let lines: Vec<String> = overlaps
+    .iter()
+    .map(|overlap| positions(&[overlap.get_start(), overlap.get_end()]))
+    .collect();
+  format!(
+    "{{\"type\":\"MultiLineString\",\"coordinates\":[{}]}}",
+    lines.join(",")
+  )
+}
+
+/// The wires of `board` and their crossings as a `GeoJSON` feature
+/// collection, to view in map and geometry tools.
+///
+/// Each wire is a `LineString` feature with its number in the `wire`
+/// property, in the order of the board, followed by a `MultiPoint` feature of
+/// the points where wires cross or touch and a `MultiLineString` feature of
+/// the segments along which they run together, as `Board::meeting_points`
+/// finds them. Wires without corners have no geometry and are left out, but
+/// the others keep their numbers. Coordinates are grid units rather than
+/// degrees, with `y` negated.
+#[must_use]
+pub fn render(board: &Board) -> String {
+  let mut features: Vec<String> = board
+    .get_wires()
+    .iter()
+    .enumerate()
+    .filter_map(|(i, wire)| {
+      Some(feature(&line_string(wire)?, &format!("{{\"wire\":{i}}}")))
+    })
+    .collect();
+  let (mut crossings, mut overlaps) = (vec![], vec![]);
+  for (intersection, _) in board.meeting_points(2) {
+    match intersection {
+      Intersection::Point(p) => crossings.push(p),
+      Intersection::Overlap(overlap) => overlaps.push(overlap),
+    }
+  }
+  features.push(feature(&multi_point(&crossings), "{\"name\":\"crossings\"}"));
+  features
+    .push(feature(&multi_line_string(&overlaps), "{\"name\":\"overlaps\"}"));
+  format!(
+    "{{\"type\":\"FeatureCollection\",\"features\":[\n{}\n]}}\n",
+    features.join(",\n")
+  )
+}
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use crate::problem_3a::{Orientation, Point, Wire};
+
+/// Where a section starts and ends along its line, and its index.
+type Span = (i32, i32, usize);
+
+/// The line through `point` with the given orientation, as the orientation
+/// and the coordinate that is the same for every point on it, and where
+/// `point` lies along it.
+pub(crate) fn line_through(
+  orientation: &Orientation,
+  point: Point,
+) -> ((Orientation, i64), i32) {
+  let (x, y) = (point.get_x(), point.get_y());
+  let key = match orientation {
+    Orientation::Horizontal => i64::from(y),
+    Orientation::Vertical => i64::from(x),
+    Orientation::Diagonal => i64::from(y) - i64::from(x),
+    Orientation::AntiDiagonal => i64::from(y) + i64::from(x),
+  };
+  let along = if *orientation == Orientation::Vertical { y } else { x };
+  ((orientation.clone(), key), along)
+}
+
+/// The point `along` the line `(orientation, key)`, as `line_through` finds
+/// them.
+///
+/// # Panics
+///
+/// Panics if the point does not fit in an `i32`.
+pub(crate) fn point_on(
+  (orientation, key): &(Orientation, i64),
+  along: i32,
+) -> Point {
+  let y = match orientation {
+    Orientation::Horizontal => *key,
+    Orientation::Vertical => i64::from(along),
+    Orientation::Diagonal => key + i64::from(along),
+    Orientation::AntiDiagonal => key - i64::from(along),
+  };
+  let y = i32::try_from(y).expect("A point on a line fits in an i32");
+  if *orientation == Orientation::Vertical {
+    let x = i32::try_from(*key).expect("A point on a line fits in an i32");
+    Point::new(x, along)
+  } else {
+    Point::new(along, y)
+  }
+}
+
+/// The sections of a wire that lie on one line.
+#[derive(Debug, Default)]
+struct Line {
+  /// Sorted by where they start.
+  spans: Vec<Span>,
+  /// The furthest that any of the first `i + 1` spans reaches, for each `i`.
+  reach: Vec<i32>,
+}
+
+/// The sections of a wire grouped by the line they lie on, to find the ones
+/// that pass through a point without looking at every section, as
+/// `Wire::steps_to` does.
+///
+/// Within a line, the spans that start at or before a point are checked from
+/// the last one back, stopping once none of the earlier ones reach it. That
+/// takes `O(log n + k)` time for `k` sections through the point, unless many
+/// long sections run along the same line.
+#[derive(Debug)]
+pub struct SectionIndex<'a> {
+  wire: &'a Wire,
+  lines: BTreeMap<(Orientation, i64), Line>,
+}
+
+impl<'a> SectionIndex<'a> {
+  /// Index the sections of `wire`, in `O(n log n)` time.
+  #[must_use]
+  pub fn new(wire: &'a Wire) -> Self {
+    let mut lines: BTreeMap<(Orientation, i64), Line> = BTreeMap::new();
+    for (i, section) in wire.get_sections().iter().enumerate() {
+      let orientation = section.get_orientation();
+      let (key, start) = line_through(&orientation, section.get_start());
+      let (_, end) = line_through(&orientation, section.get_end());
+      lines.entry(key).or_default().spans.push((start, end, i));
+    }
+    for line in lines.values_mut() {
+      line.spans.sort_unstable();
+      line.reach = line
+        .spans
+        .iter()
+        .scan(i32::MIN, |reach, &(_, end, _)| {
+          *reach = end.max(*reach);
+          Some(*reach)
+        })
+        .collect();
+    }
+    Self { wire, lines }
+  }
+
+  #[must_use]
+  pub const fn get_wire(&self) -> &Wire { self.wire }
+
+  /// The indices of the sections of the wire that pass through `point`, in
+  /// order.
+  #[must_use]
+  pub fn sections_at(&self, point: Point) -> Vec<usize> {
+    let orientations = [
+      Orientation::Vertical,
+      Orientation::Horizontal,
+      Orientation::Diagonal,
+      Orientation::AntiDiagonal,
+    ];
+    let mut found = Vec::new();
+    for orientation in &orientations {
+      let (key, along) = line_through(orientation, point);
+      if let Some(line) = self.lines.get(&key) {
+        let before = line.spans.partition_point(|&(start, ..)| start <= along);
+        for i in (0..before).rev() {
+          if line.reach[i] < along {
+            break;
+          }
+          if line.spans[i].1 >= along {
+            found.push(line.spans[i].2);
+          }
+        }
+      }
+    }
+    found.sort_unstable();
+    found
+  }
+
+  /// Fewest steps along the wire needed to reach `point`, or `None` if the
+  /// wire does not pass through it, the same as `Wire::steps_to`.
+  #[must_use]
+  pub fn steps_to(&self, point: &Point) -> Option<i64> {
+    // Sections are reached in order, so the first one through the point is
+    // the one that gets there in the fewest steps.
+    let i = *self.sections_at(*point).first()?;
+    let entry = self.wire.get_corners()[i];
+    Some(self.wire.get_step_counts()[i] + entry.steps_along(point))
+  }
+}
@@ -1,6 +1,24 @@
 #![deny(clippy::all, clippy::nursery, clippy::pedantic)]
 #![forbid(unsafe_code)]
-#![feature(try_find)]
+
+/// Any number of wires on one grid, such as the traces of a circuit board,
+/// and the points where several of them meet.
+pub mod board;
+
+/// Drawing wires as text, like the diagrams in the puzzle statement.
+pub mod diagram;
+
+/// Writing wires and their crossings as `GeoJSON`, to view them in map and
+/// geometry tools.
+pub mod geojson;
+
+/// Finding the sections of a wire that pass through a point, with the
+/// sections grouped by the line they lie on.
+pub mod index;
+
+/// Ways of measuring distance to rank crossings by: Manhattan distance, as
+/// in the puzzle, Chebyshev distance and squared Euclidean distance.
+pub mod metric;
 
 /// Strategy:
 ///
@@ -8,14 +26,57 @@
 ///   - This is represented in its entirety by a `Path`.
 ///     - Each entry, separated by commas, is a `PathEntry`, which consists of 2
 ///       items:
-///       - `MovementDirection` that represents up, down, left, or right.
+///       - `MovementDirection` that represents up, down, left, right, or a
+///         diagonal such as `UL` for up and to the left.
 ///       - Distance represented by an `i32`.
+///     - Entries that cannot be parsed are reported as `ParseError`s, which
+///       give the index of the entry and the bytes that are wrong.
 /// - A `Path` can be turned into a `Wire`.
 /// - A `Wire` is a series of segments that represents the main item in the
 ///   problem.
 ///     - A `Wire` is composed of one or more `WireSection`s.  Each
 ///       `WireSection` is composed of 3 parts:
-///         - An `Orientation`, which is either vertical or horizontal.
+///         - An `Orientation`: vertical, horizontal or one of the two
+///           diagonals.
 ///         - A start `Point` (x and y `i32` coordinates).
 ///         - An end `Point` (x and y `i32` coordinates).
+///       - The start is the end with the lower `x` or `y` coordinate.
+/// - Distances are Manhattan distances, calculated in `i64`, from the origin of
+///   a `Frame`. The default frame is centred on the central port at `(0,0)`,
+///   `Point::ORIGIN`. `solve_by` and `Frame::closest_crossing_by` rank
+///   crossings by any other `Metric` instead.
+///     - Converting a `Path` walks it from the central port at `(0,0)`, with
+///       `y` increasing downwards, and records the steps taken to reach each
+///       `WireSection`.
+/// - The crossings of two `Wire`s are the intersections of every pair of their
+///   `WireSection`s. `solve` picks the one closest to the central port (Problem
+///   3a) or the one reached in the fewest combined steps (Problem 3b).
+///     - Collinear `WireSection`s meet in an `Overlap`, which keeps the whole
+///       shared segment, so `overlaps` can report where two wires are shorted
+///       together along their length.
+/// - A single `Wire` can also be checked against itself: `self_crossings` finds
+///   where it crosses or doubles back over itself, `first_revisit` the first
+///   point it comes back to, and `twice_enclosed_area` twice the area of a
+///   closed loop, by the shoelace formula.
+/// - With the `serde` feature, paths, points and wires can be stored as JSON
+///   and read back.
 pub mod problem_3a;
+
+/// Routing a new wire between two points around the existing ones, by A*
+/// search over a grid of only the coordinates where a route may need to
+/// turn.
+pub mod route;
+
+/// Drawing wires as SVG images, with their crossings and the closest one
+/// marked, to see why an answer is wrong.
+pub mod svg;
+
+/// Finding every crossing of two wires with a sweep line, for wires with too
+/// many sections to compare every pair as `Wire::crossings` does.
+///
+/// Horizontal sections are swept along `x`, kept in an ordered set by `y`
+/// while the sweep is between their ends, and each vertical section is
+/// compared only with the active horizontal sections it spans. Collinear
+/// sections are grouped by the line they lie on and swept along it. Diagonal
+/// sections are compared with every section of the other wire.
+pub mod sweep;
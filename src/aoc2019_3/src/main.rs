@@ -0,0 +1,260 @@
+//! Usage: `aoc2019_3 [OPTION] [INPUT]`
+//!
+//! Reads the wire paths from `INPUT`, or from stdin if it is missing. With no
+//! option, prints the answers to both parts of the puzzle. Otherwise:
+//!
+//! - `--mode distance|steps`: print only the answer to part 1 or part 2.
+//! - `--metric NAME`: pick the closest crossing by `manhattan` (the default),
+//!   `chebyshev` or `squared-euclidean`.
+//! - `--min-wires K`: list every point where at least `K` wires meet, and which
+//!   wires they are.
+//! - `--overlaps`: list every segment the two wires share, with its length,
+//!   then every point on those segments.
+//! - `--loops`: for each wire, list where it crosses or overlaps itself, the
+//!   first point it comes back to, and the area it encloses if it is closed.
+//! - `--svg`: draw the wires as an SVG image.
+//! - `--geojson`: write the wires and their crossings as GeoJSON.
+//! - `--diagram`: draw the wires as text, like the puzzle's diagrams.
+//! - `--crop X,Y,R`: draw as text only the cells within `R` of `(X, Y)`.
+//! - `--route X1,Y1,X2,Y2`: print the shortest path from `(X1, Y1)` to `(X2,
+//!   Y2)` that keeps clear of every wire, in the same form as the input.
+
+use aoc2019_3::board::Board;
+use aoc2019_3::metric::{self, Manhattan, Metric};
+use aoc2019_3::problem_3a::{
+  Frame, Intersection, Point, SolverMode, Wire, overlap_points, overlaps,
+  parse_input, parse_paths, solve_by,
+};
+use aoc2019_3::{diagram, geojson, svg};
+use std::convert::TryFrom;
+use std::io::Read;
+use std::process::exit;
+
+/// Read the puzzle input from `path`, or from stdin if there is none.
+fn read_input(path: Option<&str>) -> Result<String, String> {
+  match path {
+    Some(path) =>
+      std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}")),
+    None => {
+      let mut buffer = String::new();
+      std::io::stdin()
+        .read_to_string(&mut buffer)
+        .map_err(|e| format!("stdin: {e}"))?;
+      Ok(buffer)
+    },
+  }
+}
+
+/// What the command line asks for.
+enum Command {
+  /// Solve the puzzle in each mode, measuring distances by the metric.
+  Solve(Vec<SolverMode>, &'static dyn Metric),
+  /// List the points and segments where at least this many wires meet.
+  MinWires(usize),
+  /// List the segments along which the two wires run together, and every
+  /// point on them.
+  Overlaps,
+  /// List where each wire crosses itself and whether it is a closed loop.
+  Loops,
+  /// Draw the wires as an SVG image.
+  Svg,
+  /// Write the wires and their crossings as GeoJSON.
+  GeoJson,
+  /// Draw the wires as text, all of them or only the square within a radius
+  /// of a point.
+  Diagram(Option<(Point, i32)>),
+  /// Print the shortest path between two points that keeps clear of the
+  /// wires.
+  Route(Point, Point),
+}
+
+const USAGE: &str = "Usage: aoc2019_3 [--mode distance|steps | --metric NAME \
+                     | --min-wires K | --overlaps | --loops | --svg | \
+                     --geojson | --diagram | --crop X,Y,R | --route \
+                     X1,Y1,X2,Y2] [INPUT]";
+
+/// Parse the `X,Y,R` argument of `--crop`.
+fn parse_crop(s: &str) -> Result<(Point, i32), String> {
+  let numbers = s
+    .split(',')
+    .map(str::parse)
+    .collect::<Result<Vec<i32>, _>>()
+    .map_err(|_| format!("Not X,Y,R: {s}"))?;
+  match numbers.as_slice() {
+    &[x, y, radius] if radius >= 0 => Ok((Point::new(x, y), radius)),
+    _ => Err(format!("Not X,Y,R: {s}")),
+  }
+}
+
+/// Parse the `X1,Y1,X2,Y2` argument of `--route`.
+fn parse_route(s: &str) -> Result<(Point, Point), String> {
+  let numbers = s
+    .split(',')
+    .map(str::parse)
+    .collect::<Result<Vec<i32>, _>>()
+    .map_err(|_| format!("Not X1,Y1,X2,Y2: {s}"))?;
+  match numbers.as_slice() {
+    &[x1, y1, x2, y2] => Ok((Point::new(x1, y1), Point::new(x2, y2))),
+    _ => Err(format!("Not X1,Y1,X2,Y2: {s}")),
+  }
+}
+
+/// Parse the command line into a command and the input path.
+fn parse_args(args: &[String]) -> Result<(Command, Option<&str>), String> {
+  let (command, rest) = match args {
+    [flag, mode, rest @ ..] if flag == "--mode" =>
+      (Command::Solve(vec![mode.parse()?], &Manhattan), rest),
+    [flag, name, rest @ ..] if flag == "--metric" => (
+      Command::Solve(
+        vec![SolverMode::ClosestDistance, SolverMode::FewestSteps],
+        metric::named(name)?,
+      ),
+      rest,
+    ),
+    [flag, k, rest @ ..] if flag == "--min-wires" => (
+      Command::MinWires(
+        k.parse().map_err(|_| format!("Not a number of wires: {k}"))?,
+      ),
+      rest,
+    ),
+    [flag, rest @ ..] if flag == "--overlaps" => (Command::Overlaps, rest),
+    [flag, rest @ ..] if flag == "--loops" => (Command::Loops, rest),
+    [flag, rest @ ..] if flag == "--svg" => (Command::Svg, rest),
+    [flag, rest @ ..] if flag == "--geojson" => (Command::GeoJson, rest),
+    [flag, rest @ ..] if flag == "--diagram" => (Command::Diagram(None), rest),
+    [flag, crop, rest @ ..] if flag == "--crop" =>
+      (Command::Diagram(Some(parse_crop(crop)?)), rest),
+    [flag, ends, rest @ ..] if flag == "--route" => {
+      let (start, goal) = parse_route(ends)?;
+      (Command::Route(start, goal), rest)
+    },
+    rest => (
+      Command::Solve(
+        vec![SolverMode::ClosestDistance, SolverMode::FewestSteps],
+        &Manhattan,
+      ),
+      rest,
+    ),
+  };
+  match rest {
+    [] => Ok((command, None)),
+    [path] if !path.starts_with("--") => Ok((command, Some(path.as_str()))),
+    _ => Err(USAGE.into()),
+  }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+  let (command, path) = parse_args(args)?;
+  let input = read_input(path)?;
+  match command {
+    Command::Solve(modes, metric) =>
+      for mode in modes {
+        let answer = solve_by(&input, mode, metric)?;
+        match mode {
+          SolverMode::ClosestDistance => println!("Problem 3a: {answer}"),
+          SolverMode::FewestSteps => println!("Problem 3b: {answer}"),
+        }
+      },
+    Command::MinWires(k) => {
+      let board = input.parse::<Board>()?;
+      for (intersection, wires) in board.meeting_points(k) {
+        match intersection {
+          Intersection::Point(p) =>
+            println!("({}, {}): wires {:?}", p.get_x(), p.get_y(), wires),
+          Intersection::Overlap(overlap) => {
+            let (start, end) = (overlap.get_start(), overlap.get_end());
+            println!(
+              "({}, {}) to ({}, {}): wires {:?}",
+              start.get_x(),
+              start.get_y(),
+              end.get_x(),
+              end.get_y(),
+              wires
+            );
+          },
+        }
+      }
+    },
+    Command::Overlaps => {
+      let (path1, path2) = parse_input(&input)?;
+      let (wire1, wire2) = (Wire::try_from(&path1)?, Wire::try_from(&path2)?);
+      for overlap in overlaps(&wire1, &wire2) {
+        let (start, end) = (overlap.get_start(), overlap.get_end());
+        println!(
+          "({}, {}) to ({}, {}): length {}",
+          start.get_x(),
+          start.get_y(),
+          end.get_x(),
+          end.get_y(),
+          overlap.length()
+        );
+      }
+      for point in overlap_points(&wire1, &wire2) {
+        println!("({}, {})", point.get_x(), point.get_y());
+      }
+    },
+    Command::Loops =>
+      for (i, path) in parse_paths(&input)?.iter().enumerate() {
+        let wire = Wire::try_from(path)?;
+        for crossing in wire.self_crossings() {
+          match crossing {
+            Intersection::Point(p) =>
+              println!("Wire {i}: ({}, {})", p.get_x(), p.get_y()),
+            Intersection::Overlap(o) => {
+              let (start, end) = (o.get_start(), o.get_end());
+              println!(
+                "Wire {i}: ({}, {}) to ({}, {}): length {}",
+                start.get_x(),
+                start.get_y(),
+                end.get_x(),
+                end.get_y(),
+                o.length()
+              );
+            },
+          }
+        }
+        match wire.first_revisit() {
+          Some((p, steps)) => println!(
+            "Wire {i}: first revisits ({}, {}) after {steps} steps",
+            p.get_x(),
+            p.get_y()
+          ),
+          None => println!("Wire {i}: never revisits a point"),
+        }
+        match wire.twice_enclosed_area() {
+          Some(twice) => {
+            let half = if twice % 2 == 0 { "" } else { ".5" };
+            println!("Wire {i}: closed, enclosing area {}{half}", twice / 2);
+          },
+          None => println!("Wire {i}: open"),
+        }
+      },
+    Command::Diagram(None) => print!("{}", diagram::render(&input.parse()?)),
+    Command::Diagram(Some((centre, radius))) =>
+      print!("{}", diagram::render_around(&input.parse()?, centre, radius)),
+    Command::Svg => print!("{}", svg::render(&input.parse()?, Frame::DEFAULT)),
+    Command::GeoJson => print!("{}", geojson::render(&input.parse()?)),
+    Command::Route(start, goal) => {
+      let board = input.parse::<Board>()?;
+      let path = board.route(start, goal).ok_or_else(|| {
+        format!(
+          "No route from ({}, {}) to ({}, {})",
+          start.get_x(),
+          start.get_y(),
+          goal.get_x(),
+          goal.get_y()
+        )
+      })?;
+      println!("{path}");
+    },
+  }
+  Ok(())
+}
+
+fn main() {
+  let args: Vec<String> = std::env::args().skip(1).collect();
+  if let Err(e) = run(&args) {
+    eprintln!("{e}");
+    exit(1);
+  }
+}
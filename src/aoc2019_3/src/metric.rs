@@ -0,0 +1,92 @@
+use std::fmt::Debug;
+
+use crate::problem_3a::Point;
+
+/// A way of measuring how far apart two points are, used to rank crossings
+/// by how close they are to the origin of a `Frame`.
+///
+/// Distances are `i128`s, so that none of the metrics here can overflow for
+/// any two points. A metric must give 0 from a point to itself and the same
+/// distance in both directions, and must never rise and then fall again
+/// along a straight line, as is true of the distance given by any norm, so
+/// that `Overlap::closest_by` can search an overlap for its closest point.
+pub trait Metric: Debug {
+  /// The distance from `from` to `to`.
+  fn distance(&self, from: Point, to: Point) -> i128;
+
+  /// Whichever of the two points is closer to `origin`, preferring `point1`
+  /// if they are equally close.
+  fn closer<'a>(
+    &self,
+    origin: Point,
+    point1: &'a Point,
+    point2: &'a Point,
+  ) -> &'a Point {
+    if self.distance(origin, *point2) < self.distance(origin, *point1) {
+      point2
+    } else {
+      point1
+    }
+  }
+}
+
+/// The differences between two points in `x` and in `y`, without their signs.
+fn differences(from: Point, to: Point) -> (i128, i128) {
+  (
+    (i128::from(to.get_x()) - i128::from(from.get_x())).abs(),
+    (i128::from(to.get_y()) - i128::from(from.get_y())).abs(),
+  )
+}
+
+/// The sum of the differences in `x` and `y`, which is the number of steps
+/// between two points moving only horizontally and vertically. This is the
+/// distance used by the puzzle.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Manhattan;
+
+impl Metric for Manhattan {
+  fn distance(&self, from: Point, to: Point) -> i128 {
+    let (dx, dy) = differences(from, to);
+    dx + dy
+  }
+}
+
+/// The larger of the differences in `x` and `y`, which is the number of steps
+/// between two points when diagonal moves are allowed, as counted by
+/// `Point::steps_along`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Chebyshev;
+
+impl Metric for Chebyshev {
+  fn distance(&self, from: Point, to: Point) -> i128 {
+    let (dx, dy) = differences(from, to);
+    dx.max(dy)
+  }
+}
+
+/// The square of the straight-line distance between two points, which ranks
+/// points the same way as the distance itself but stays an integer.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SquaredEuclidean;
+
+impl Metric for SquaredEuclidean {
+  fn distance(&self, from: Point, to: Point) -> i128 {
+    let (dx, dy) = differences(from, to);
+    dx * dx + dy * dy
+  }
+}
+
+/// The metric called `name`: `manhattan`, `chebyshev` or `squared-euclidean`.
+///
+/// # Errors
+///
+/// Returns an error if `name` is not one of those.
+pub fn named(name: &str) -> Result<&'static dyn Metric, String> {
+  match name {
+    "manhattan" => Ok(&Manhattan),
+    "chebyshev" => Ok(&Chebyshev),
+    "squared-euclidean" => Ok(&SquaredEuclidean),
+    _ =>
+      Err(format!("Not one of manhattan, chebyshev, squared-euclidean: {name}")),
+  }
+}
@@ -1,29 +1,155 @@
+use std::cmp::min_by_key;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
+use std::num::IntErrorKind;
+use std::ops::Range;
 use std::str::FromStr;
 
-use crate::problem_3a::MovementDirection::{D, L, R, U};
-use crate::problem_3a::Orientation::{Horizontal, Vertical};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::index::SectionIndex;
+use crate::metric::{Manhattan, Metric};
+use crate::problem_3a::MovementDirection::{D, DL, DR, L, R, U, UL, UR};
+use crate::problem_3a::Orientation::{
+  AntiDiagonal, Diagonal, Horizontal, Vertical,
+};
+use crate::sweep;
 
 /// Stand-alone function to split a string into two: the first character (as a
 /// string) and the remainder. Avoids errors caused due to empty strings.
 fn car_cdr(s: &str) -> (&str, &str) {
-  match s.chars().next() {
-    Some(c) => s.split_at(c.len_utf8()),
-    None => s.split_at(0),
-  }
+  s.chars().next().map_or_else(|| s.split_at(0), |c| s.split_at(c.len_utf8()))
 }
 
 // =============================================================================
 // Section for `Path`
 // =============================================================================
 
-/// The direction to move in to create the next `WireSection`
-#[derive(Clone, Debug, PartialEq)]
+/// What is wrong with an entry of a `Path`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseErrorKind {
+  /// Nothing between two commas, or before the first one.
+  EmptyEntry,
+  /// The entry does not start with one of `U`, `D`, `L`, `R`, `UL`, `UR`,
+  /// `DL` or `DR`.
+  BadDirection(String),
+  /// The direction is not followed by a number.
+  BadDistance(String),
+  /// The distance is zero, so the move goes nowhere.
+  ZeroDistance,
+  /// The distance is too large for an `i32`.
+  Overflow(String),
+}
+
+impl Display for ParseErrorKind {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::EmptyEntry => write!(f, "Empty entry"),
+      Self::BadDirection(s) =>
+        write!(f, "Not one of U, D, L, R, UL, UR, DL, DR: {s}"),
+      Self::BadDistance(s) => write!(f, "Not a distance: {s}"),
+      Self::ZeroDistance => write!(f, "Zero distance not allowed"),
+      Self::Overflow(s) => write!(f, "Distance too large: {s}"),
+    }
+  }
+}
+
+/// An error in parsing a `Path`, a `PathEntry` or a `MovementDirection`:
+/// what is wrong, the index of the entry it is in, counting from 0, and the
+/// bytes of the input that are wrong.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+  kind: ParseErrorKind,
+  entry: usize,
+  span: Range<usize>,
+}
+
+impl ParseError {
+  #[must_use]
+  pub const fn new(
+    kind: ParseErrorKind,
+    entry: usize,
+    span: Range<usize>,
+  ) -> Self {
+    Self { kind, entry, span }
+  }
+
+  #[must_use]
+  pub const fn get_kind(&self) -> &ParseErrorKind { &self.kind }
+
+  #[must_use]
+  pub const fn get_entry(&self) -> usize { self.entry }
+
+  #[must_use]
+  pub fn get_span(&self) -> Range<usize> { self.span.clone() }
+
+  /// The same error found in entry number `entry`, which starts `offset`
+  /// bytes into the input.
+  fn in_entry(self, entry: usize, offset: usize) -> Self {
+    Self::new(
+      self.kind,
+      entry,
+      self.span.start + offset..self.span.end + offset,
+    )
+  }
+
+  /// `line`, the input this error was found in, with the bytes that are
+  /// wrong marked with `^` on the line below. An empty span is marked with a
+  /// single `^` where it starts.
+  #[must_use]
+  pub fn underline(&self, line: &str) -> String {
+    let width = |s: Option<&str>| s.map_or(0, |s| s.chars().count());
+    let indent = width(line.get(..self.span.start));
+    let carets = width(line.get(self.span.clone())).max(1);
+    format!("{line}\n{}{}", " ".repeat(indent), "^".repeat(carets))
+  }
+}
+
+impl Display for ParseError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{} in entry {} at bytes {}..{}",
+      self.kind, self.entry, self.span.start, self.span.end
+    )
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The direction to move in to create the next `WireSection`: up, down,
+/// left, right, or one of the four diagonals, such as `UL` for up and to the
+/// left.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MovementDirection {
   U,
   D,
   L,
   R,
+  UL,
+  UR,
+  DL,
+  DR,
+}
+
+impl MovementDirection {
+  /// The direction that goes straight back.
+  #[must_use]
+  pub const fn opposite(&self) -> Self {
+    match self {
+      U => D,
+      D => U,
+      L => R,
+      R => L,
+      UL => DR,
+      UR => DL,
+      DL => UR,
+      DR => UL,
+    }
+  }
 }
 
 impl Display for MovementDirection {
@@ -33,31 +159,47 @@ impl Display for MovementDirection {
       D => "D",
       L => "L",
       R => "R",
+      UL => "UL",
+      UR => "UR",
+      DL => "DL",
+      DR => "DR",
     };
-    write!(f, "{}", s)
+    write!(f, "{s}")
   }
 }
 
 impl FromStr for MovementDirection {
-  type Err = String;
+  type Err = ParseError;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    if s.is_empty() || s.len() > 1 {
-      Err(format!("Value is not a single character: {}", s))
-    } else {
-      match s {
-        "u" | "U" => Ok(U),
-        "d" | "D" => Ok(D),
-        "l" | "L" => Ok(L),
-        "r" | "R" => Ok(R),
-        _ => Err(format!("Not one of U, D, L, R: {}", s)),
-      }
+    match s.to_ascii_uppercase().as_str() {
+      "U" => Ok(U),
+      "D" => Ok(D),
+      "L" => Ok(L),
+      "R" => Ok(R),
+      "UL" => Ok(UL),
+      "UR" => Ok(UR),
+      "DL" => Ok(DL),
+      "DR" => Ok(DR),
+      _ => Err(ParseError::new(
+        ParseErrorKind::BadDirection(s.into()),
+        0,
+        0..s.len(),
+      )),
     }
   }
 }
 
 /// Represents one entry in the `Path`
-#[derive(Clone, Debug, PartialEq)]
+///
+/// With the `serde` feature, an entry is stored as its direction and
+/// distance, and checked by parsing it when it is read back.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(try_from = "StoredEntry", into = "StoredEntry")
+)]
 pub struct PathEntry {
   direction: MovementDirection,
   distance: i32,
@@ -77,42 +219,164 @@ impl PathEntry {
 }
 
 impl FromStr for PathEntry {
-  type Err = String;
+  type Err = ParseError;
 
+  /// Parses a direction followed by a positive distance in decimal digits,
+  /// such as `R8` or `UL3`. The span of an error covers only the part of `s`
+  /// that is wrong.
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    if s.len() < 2 {
-      Err(format!("String not of type '[UDLR][0-9]+': {}", s))
-    } else {
-      let (md_str, dist_str) = car_cdr(s);
-      let direction = md_str.parse::<MovementDirection>();
-      let distance = dist_str.parse::<i32>();
-
-      match (direction, distance) {
-        (Err(_), _) => Err(format!("Unable to parse direction from {}", s)),
-        (_, Err(_)) => Err(format!("Unable to parse distance from {}", s)),
-        (Ok(dir), Ok(dist)) =>
-          if dist > 0 {
-            Ok(Self::new(dir, dist))
-          } else {
-            Err(format!("Zero distance not allowed for PathEntry: {}", s))
-          },
-      }
+    if s.is_empty() {
+      return Err(ParseError::new(ParseErrorKind::EmptyEntry, 0, 0..0));
+    }
+    // A diagonal direction takes two characters, and any other one.
+    let (md_str, dist_str) = match s.get(..2) {
+      Some(md) if md.parse::<MovementDirection>().is_ok() => s.split_at(2),
+      _ => car_cdr(s),
+    };
+    let direction = md_str.parse::<MovementDirection>()?;
+
+    let error = |kind| Err(ParseError::new(kind, 0, md_str.len()..s.len()));
+    if dist_str.is_empty() || !dist_str.bytes().all(|b| b.is_ascii_digit()) {
+      return error(ParseErrorKind::BadDistance(dist_str.into()));
+    }
+    match dist_str.parse::<i32>() {
+      Ok(0) => error(ParseErrorKind::ZeroDistance),
+      Ok(distance) => Ok(Self::new(direction, distance)),
+      Err(e) if *e.kind() == IntErrorKind::PosOverflow =>
+        error(ParseErrorKind::Overflow(dist_str.into())),
+      Err(_) => error(ParseErrorKind::BadDistance(dist_str.into())),
     }
   }
 }
 
 /// Path of a wire, given in a relative style.
-#[derive(Debug, PartialEq)]
+///
+/// With the `serde` feature, a path is stored as its entries, and checked by
+/// parsing them when it is read back, so that errors name the entry.
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(try_from = "StoredPath")
+)]
 pub struct Path {
   pub moves: Vec<PathEntry>,
 }
 
 impl Path {
   #[must_use]
-  pub fn new_internal(moves: Vec<PathEntry>) -> Self { Self { moves } }
+  pub const fn new_internal(moves: Vec<PathEntry>) -> Self { Self { moves } }
 
+  /// Parse a `Path` from a string such as `R8,U5,L5,D3`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the string is not a valid `Path`.
   #[must_use]
   pub fn new(s: &str) -> Self { s.parse::<Self>().unwrap() }
+
+  /// Parse a `Path` from a string such as `R8,U5,L5,D3`, without stopping at
+  /// the first bad entry.
+  ///
+  /// Whitespace around entries is ignored, as is a trailing comma, but any
+  /// other empty entry is an error. The span of each error is relative to the
+  /// start of `s`.
+  ///
+  /// # Errors
+  ///
+  /// Returns every error in `s`, in order, if it is not a valid `Path`.
+  pub fn try_new(s: &str) -> Result<Self, Vec<ParseError>> {
+    let fields: Vec<&str> = s.split(',').collect();
+    let mut moves = Vec::with_capacity(fields.len());
+    let mut errors = Vec::new();
+    let mut offset = 0;
+    for (i, field) in fields.iter().enumerate() {
+      let entry = field.trim();
+      let start = offset + (field.len() - field.trim_start().len());
+      offset += field.len() + 1;
+      if entry.is_empty() && i + 1 == fields.len() {
+        continue;
+      }
+      match entry.parse::<PathEntry>() {
+        Ok(e) => moves.push(e),
+        Err(e) => errors.push(e.in_entry(i, start)),
+      }
+    }
+    if errors.is_empty() { Ok(Self::new_internal(moves)) } else { Err(errors) }
+  }
+
+  /// This path with consecutive moves in the same direction merged, and
+  /// moves that go straight back cancelled against the moves before them, so
+  /// `R3,R4` becomes `R7` and `R5,L2` becomes `R3`.
+  ///
+  /// The normalised path ends at the same point, and none of its moves
+  /// retraces the one before, but the points passed while backtracking are
+  /// dropped, so it may trace fewer points than this one. Normalising it
+  /// again changes nothing.
+  #[must_use]
+  pub fn normalise(&self) -> Self {
+    let mut moves: Vec<PathEntry> = Vec::with_capacity(self.moves.len());
+    for entry in &self.moves {
+      let mut entry = entry.clone();
+      while let Some(last) = moves.last_mut() {
+        if last.direction == entry.direction {
+          // A move too far to merge stays as two.
+          if let Some(distance) = last.distance.checked_add(entry.distance) {
+            last.distance = distance;
+            entry.distance = 0;
+          }
+          break;
+        } else if last.direction == entry.direction.opposite() {
+          if last.distance > entry.distance {
+            last.distance -= entry.distance;
+            entry.distance = 0;
+            break;
+          }
+          // The rest of this move may cancel against the one before.
+          entry.distance -= last.distance;
+          moves.pop();
+        } else {
+          break;
+        }
+      }
+      if entry.distance > 0 {
+        moves.push(entry);
+      }
+    }
+    Self::new_internal(moves)
+  }
+
+  /// The path walked backwards, from the end of this path to its start: the
+  /// same moves in the opposite directions and in reverse order.
+  #[must_use]
+  pub fn reverse(&self) -> Self {
+    Self::new_internal(
+      self
+        .moves
+        .iter()
+        .rev()
+        .map(|e| PathEntry::new(e.direction.opposite(), e.distance))
+        .collect(),
+    )
+  }
+
+  /// This path followed by `other`, continuing from where this path ends.
+  #[must_use]
+  pub fn concat(&self, other: &Self) -> Self {
+    Self::new_internal(self.moves.iter().chain(&other.moves).cloned().collect())
+  }
+
+  /// Whether the two paths, both walked from the central port, pass through
+  /// the same points, whatever the order or the number of times.
+  ///
+  /// # Panics
+  ///
+  /// Panics if either path leaves the `i32` grid, like `Wire::starting_at`.
+  #[must_use]
+  pub fn same_points(&self, other: &Self) -> bool {
+    Wire::starting_at(Point::ORIGIN, self)
+      .same_points(&Wire::starting_at(Point::ORIGIN, other))
+  }
 }
 
 impl Display for Path {
@@ -120,44 +384,72 @@ impl Display for Path {
     // NOTE: Requires 2 lines instead of chaining the entire express because
     // of a lifetime issue E0716
     let s = self.moves.iter().fold(String::new(), |acc, e| {
-      format!("{},{}{}", acc, e.direction, e.distance)
+      format!("{acc},{}{}", e.direction, e.distance)
     });
 
     // Splits the string and gets the 2nd half. Per the API, this will not
     // error out even if the string being split is empty.
     let s = car_cdr(s.as_str()).1;
 
-    write!(f, "{}", s)
+    write!(f, "{s}")
   }
 }
 
 impl FromStr for Path {
-  type Err = String;
+  type Err = ParseError;
 
+  /// Parses a `Path` like `Path::try_new`, but reports only the first error.
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    let moves: Vec<Result<PathEntry, _>> = s
-      .chars()
-      .filter(|c: &char| !c.is_whitespace())
-      .collect::<String>()
-      .split(',')
-      .filter_map(|e: &str| {
-        if e.is_empty() { None } else { Some(e.parse::<PathEntry>()) }
-      })
-      .collect();
+    Self::try_new(s).map_err(|errors| errors[0].clone())
+  }
+}
 
-    let any_errors = moves.iter().any(std::result::Result::is_err);
-    if any_errors {
-      Err(format!(
-        "Unable to create Path from {}, {:?}",
-        s,
-        moves.iter().find(|e| e.is_err())
-      ))
-    } else {
-      let clean_moves: Vec<PathEntry> =
-        moves.iter().map(|e| e.as_ref().unwrap()).cloned().collect();
+/// A `PathEntry` as the `serde` feature stores it, before it is checked.
+#[cfg(feature = "serde")]
+#[derive(Deserialize, Serialize)]
+struct StoredEntry {
+  direction: MovementDirection,
+  distance: i32,
+}
 
-      Ok(Self::new_internal(clean_moves))
-    }
+/// A `Path` as the `serde` feature stores it, before it is checked.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct StoredPath {
+  moves: Vec<StoredEntry>,
+}
+
+#[cfg(feature = "serde")]
+impl Display for StoredEntry {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}{}", self.direction, self.distance)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl From<PathEntry> for StoredEntry {
+  fn from(entry: PathEntry) -> Self {
+    Self { direction: entry.direction, distance: entry.distance }
+  }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<StoredEntry> for PathEntry {
+  type Error = ParseError;
+
+  fn try_from(entry: StoredEntry) -> Result<Self, Self::Error> {
+    entry.to_string().parse()
+  }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<StoredPath> for Path {
+  type Error = ParseError;
+
+  fn try_from(path: StoredPath) -> Result<Self, Self::Error> {
+    let entries: Vec<String> =
+      path.moves.iter().map(ToString::to_string).collect();
+    entries.join(",").parse()
   }
 }
 
@@ -166,40 +458,294 @@ impl FromStr for Path {
 // =============================================================================
 
 /// The orientation of a section of wire
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Orientation {
   Vertical,
   Horizontal,
+  /// `y` increases with `x`, which is down and to the right.
+  Diagonal,
+  /// `y` decreases as `x` increases, which is up and to the right.
+  AntiDiagonal,
 }
 
 /// A point in a 2D plane
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Point {
   x: i32,
   y: i32,
 }
 
 impl Point {
-  pub const ORIGIN: Point = Point { x: std::i32::MIN, y: std::i32::MIN };
+  /// `(0,0)`, the central port where every `Wire` built from a `Path`
+  /// starts.
+  pub const ORIGIN: Self = Self { x: 0, y: 0 };
+
+  #[must_use]
+  pub const fn new(x: i32, y: i32) -> Self { Self { x, y } }
+
+  #[must_use]
+  pub const fn get_x(&self) -> i32 { self.x }
+
+  #[must_use]
+  pub const fn get_y(&self) -> i32 { self.y }
+
+  /// The point reached by following `entry` from this point. `y` increases
+  /// going down, so `U` decreases it. A diagonal move of distance `d` changes
+  /// both `x` and `y` by `d`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new point does not fit in an `i32`. Use `try_walk` for
+  /// moves that come from untrusted input.
+  #[must_use]
+  pub const fn walk(&self, entry: &PathEntry) -> Self {
+    match self.try_walk(entry) {
+      Some(point) => point,
+      None => panic!("Point::walk: the new point does not fit in an i32"),
+    }
+  }
+
+  /// The point reached by following `entry` from this point, like `walk`, or
+  /// `None` if it does not fit in an `i32`.
+  #[must_use]
+  pub const fn try_walk(&self, entry: &PathEntry) -> Option<Self> {
+    let d = entry.distance;
+    let (x, y) = match entry.direction {
+      U => (Some(self.x), self.y.checked_sub(d)),
+      D => (Some(self.x), self.y.checked_add(d)),
+      L => (self.x.checked_sub(d), Some(self.y)),
+      R => (self.x.checked_add(d), Some(self.y)),
+      UL => (self.x.checked_sub(d), self.y.checked_sub(d)),
+      UR => (self.x.checked_add(d), self.y.checked_sub(d)),
+      DL => (self.x.checked_sub(d), self.y.checked_add(d)),
+      DR => (self.x.checked_add(d), self.y.checked_add(d)),
+    };
+    match (x, y) {
+      (Some(x), Some(y)) => Some(Self::new(x, y)),
+      _ => None,
+    }
+  }
 
-  /// Calculates the Manhattan distance of a point to the center
+  /// Manhattan distance to `other`, which is also the number of steps along
+  /// a horizontal or vertical line to it. Calculated in `i64`, so it cannot
+  /// overflow for any two points.
+  #[must_use]
+  pub const fn steps_to(&self, other: &Self) -> i64 {
+    ((self.x as i64) - (other.x as i64)).abs()
+      + ((self.y as i64) - (other.y as i64)).abs()
+  }
+
+  /// Number of steps to `other` along a horizontal, vertical or diagonal
+  /// line, which is the larger of the differences in `x` and `y`. A wire
+  /// moves one cell per step, so this is how far a `WireSection` is along.
+  #[must_use]
+  pub const fn steps_along(&self, other: &Self) -> i64 {
+    let dx = ((self.x as i64) - (other.x as i64)).abs();
+    let dy = ((self.y as i64) - (other.y as i64)).abs();
+    if dx > dy { dx } else { dy }
+  }
+
+  /// Calculates the Manhattan distance of a point to `Point::ORIGIN`. Use a
+  /// `Frame` to measure from somewhere else.
   #[must_use]
   pub const fn manhattan_distance(&self) -> i64 {
-    ((self.x as i64) - (Point::ORIGIN.x as i64)).abs()
-      + ((self.y as i64) - (Point::ORIGIN.y as i64)).abs()
+    Frame::DEFAULT.distance(self)
   }
 
+  /// Whichever of the two points is closer to `Point::ORIGIN` by Manhattan
+  /// distance, preferring `self` if they are equidistant. `Metric::closer`
+  /// compares points by other metrics. Both references share a lifetime because
+  /// either one may be returned.
   #[must_use]
-  /// TODO understand if this lifetime parameter is correct.
-  pub fn closer_to_zero<'a>(&'a self, other_point: &'a Point) -> &'a Point {
-    let md_self = self.manhattan_distance();
-    let md_other = other_point.manhattan_distance();
+  pub const fn closer_to_zero<'a>(&'a self, other_point: &'a Self) -> &'a Self {
+    Frame::DEFAULT.closer(self, other_point)
+  }
+}
+
+/// The reference point that distances are measured from. The puzzle measures
+/// from the central port, which is `Frame::DEFAULT`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Frame {
+  origin: Point,
+}
+
+impl Frame {
+  /// A frame centred on `Point::ORIGIN`.
+  pub const DEFAULT: Self = Self { origin: Point::ORIGIN };
 
-    if md_self <= md_other { self } else { other_point }
+  #[must_use]
+  pub const fn new(origin: Point) -> Self { Self { origin } }
+
+  #[must_use]
+  pub const fn get_origin(&self) -> Point { self.origin }
+
+  /// Manhattan distance from the origin of the frame to `point`.
+  #[must_use]
+  pub const fn distance(&self, point: &Point) -> i64 {
+    self.origin.steps_to(point)
+  }
+
+  /// Whichever of the two points is closer to the origin of the frame,
+  /// preferring `point1` if they are equidistant.
+  #[must_use]
+  pub const fn closer<'a>(
+    &self,
+    point1: &'a Point,
+    point2: &'a Point,
+  ) -> &'a Point {
+    if self.distance(point1) <= self.distance(point2) { point1 } else { point2 }
+  }
+}
+
+/// Where two `WireSection`s meet.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Intersection {
+  /// The sections cross or touch at a single point.
+  Point(Point),
+  /// The sections are collinear and share a whole segment.
+  Overlap(Overlap),
+}
+
+impl Intersection {
+  /// An overlap from `start` to `end`, or a single point if they are equal.
+  const fn between(start: Point, end: Point) -> Self {
+    if start.x == end.x && start.y == end.y {
+      Self::Point(start)
+    } else {
+      Self::Overlap(Overlap { start, end })
+    }
+  }
+
+  /// Every point the sections share, from the start of an overlap to its
+  /// end.
+  #[must_use]
+  pub fn points(&self) -> Vec<Point> {
+    match self {
+      Self::Point(p) => vec![*p],
+      Self::Overlap(overlap) => overlap.points(),
+    }
+  }
+}
+
+/// The step from `from` towards `to`, as the change in `x` and in `y`, each
+/// -1, 0 or 1.
+fn heading(from: Point, to: Point) -> (i32, i32) {
+  let sign = |from: i32, to: i32| i32::from(to > from) - i32::from(to < from);
+  (sign(from.x, to.x), sign(from.y, to.y))
+}
+
+/// Whether `point` lies on the straight line from `start` to `end`, end points
+/// included. `start` must not be to the right of `end`.
+const fn on_segment(start: Point, end: Point, point: Point) -> bool {
+  let (dx, dy) = (end.x as i64 - start.x as i64, end.y as i64 - start.y as i64);
+  let (px, py) =
+    (point.x as i64 - start.x as i64, point.y as i64 - start.y as i64);
+  let (low, high) =
+    if start.y <= end.y { (start.y, end.y) } else { (end.y, start.y) };
+  dx * py == dy * px
+    && start.x <= point.x
+    && point.x <= end.x
+    && low <= point.y
+    && point.y <= high
+}
+
+/// The segment shared by two collinear `WireSection`s, such as two traces
+/// shorted together along their length.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Overlap {
+  start: Point,
+  end: Point,
+}
+
+impl Overlap {
+  /// Create an `Overlap` from `start` to `end`.
+  ///
+  /// Post-invariant: the ends are ordered as by `WireSection::create`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the points are identical or are not on the same horizontal,
+  /// vertical or diagonal line.
+  #[must_use]
+  pub fn new(start: Point, end: Point) -> Self {
+    let section = WireSection::create(start, end);
+    Self { start: section.start, end: section.end }
+  }
+
+  #[must_use]
+  pub const fn get_start(&self) -> Point { self.start }
+
+  #[must_use]
+  pub const fn get_end(&self) -> Point { self.end }
+
+  /// Number of steps from one end of the overlap to the other.
+  #[must_use]
+  pub const fn length(&self) -> i64 { self.start.steps_along(&self.end) }
+
+  /// Whether `point` lies on the overlap, end points included.
+  #[must_use]
+  pub const fn contains(&self, point: &Point) -> bool {
+    on_segment(self.start, self.end, *point)
+  }
+
+  /// The point `steps` steps along the overlap from its start.
+  fn at(&self, steps: i64) -> Point {
+    let (start, end) = (self.start, self.end);
+    let coordinate = |from: i32, to: i32| {
+      i32::try_from(
+        i64::from(from) + (i64::from(to) - i64::from(from)).signum() * steps,
+      )
+      .expect("A point on an overlap fits in an i32")
+    };
+    Point::new(coordinate(start.x, end.x), coordinate(start.y, end.y))
+  }
+
+  /// Every integer point of the overlap, from its start to its end.
+  #[must_use]
+  pub fn points(&self) -> Vec<Point> {
+    (0..=self.length()).map(|steps| self.at(steps)).collect()
+  }
+
+  /// The point of the overlap with the smallest Manhattan distance to
+  /// `point`, preferring the one nearest the start if there are several.
+  #[must_use]
+  pub fn closest_to(&self, point: Point) -> Point {
+    self.closest_by(point, &Manhattan)
+  }
+
+  /// The point of the overlap closest to `point` by `metric`, preferring the
+  /// one nearest the start if there are several.
+  ///
+  /// Along the overlap, the distance falls and then rises, without rising
+  /// and falling again, so a binary search finds the first step after which
+  /// it stops falling, in `O(log n)` for an overlap of `n` steps.
+  #[must_use]
+  pub fn closest_by(&self, point: Point, metric: &dyn Metric) -> Point {
+    let distance = |steps| metric.distance(point, self.at(steps));
+    let (mut low, mut high) = (0, self.length());
+    while low < high {
+      let middle = low + (high - low) / 2;
+      if distance(middle + 1) < distance(middle) {
+        low = middle + 1;
+      } else {
+        high = middle;
+      }
+    }
+    self.at(low)
   }
 }
 
-#[derive(Debug, PartialEq)]
+/// A straight piece of wire between two points.
+///
+/// With the `serde` feature, a section is stored as its start and end
+/// points, and checked with `WireSection::try_create` when it is read back.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(try_from = "[Point; 2]", into = "[Point; 2]")
+)]
 pub struct WireSection {
   orientation: Orientation,
   start: Point,
@@ -209,140 +755,768 @@ pub struct WireSection {
 impl WireSection {
   /// Create a `WireSection` from two `Point`s.
   ///
-  /// Test for post-invariant:
-  /// - start point < end point
+  /// Post-invariant: the start point is the one with the lower `x`, or the
+  /// lower `y` for a vertical section, whatever the order of the arguments.
+  /// The ordering does not depend on any origin, so sections compare equal in
+  /// every `Frame`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the points are identical or are not on the same horizontal,
+  /// vertical or diagonal line.
   #[must_use]
   pub fn create(point1: Point, point2: Point) -> Self {
-    let md1 = point1.manhattan_distance();
-    let md2 = point2.manhattan_distance();
+    Self::try_create(point1, point2).unwrap_or_else(|e| panic!("{}", e))
+  }
+
+  /// Create a `WireSection` from two `Point`s, like `WireSection::create`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the points are identical or are not on the same
+  /// horizontal, vertical or diagonal line.
+  pub fn try_create(point1: Point, point2: Point) -> Result<Self, String> {
+    let dx = (i64::from(point1.x) - i64::from(point2.x)).abs();
+    let dy = (i64::from(point1.y) - i64::from(point2.y)).abs();
     if point1 == point2 {
-      panic!("A WireSection cannot be created with 2 identical Points")
-    } else if point1.x != point2.x && point1.y != point2.y {
-      panic!("A WireSection must be horizontal or vertical, not diagonal")
+      return Err(
+        "A WireSection cannot be created with 2 identical Points".into(),
+      );
+    } else if dx != 0 && dy != 0 && dx != dy {
+      return Err(
+        "A WireSection must be horizontal, vertical or diagonal".into(),
+      );
     }
 
-    // One point is closer than the other to the origin or, if equidistant, then
-    // one has "lower" `x` or `y` values than the other point
-    let (sp, ep) = if md1 < md2 {
-      (point1, point2)
-    } else if md1 > md2 {
-      (point2, point1)
-    } else if point1.x < point2.x {
-      // Horizontal line
-      (point1, point2)
-    } else {
-      // Vertical line, point1.y < point2.y
-      (point2, point1)
-    };
+    // Comparing the points compares `x` and then `y`, which is the order of
+    // the points along the section whatever its orientation.
+    let (sp, ep) =
+      if point1 < point2 { (point1, point2) } else { (point2, point1) };
 
     let direction = if sp.x == ep.x {
-      Orientation::Vertical
+      Vertical
+    } else if sp.y == ep.y {
+      Horizontal
+    } else if sp.y < ep.y {
+      Diagonal
     } else {
-      Orientation::Horizontal
+      AntiDiagonal
     };
 
-    Self { orientation: direction, start: sp, end: ep }
-  }
-
-  /// Calculate the intersection of two `Point`s. As context, my mental plane
-  /// under considerations has `(0,0)` at the top-left corner, `x` increases
-  /// to the right, and `y` increases as you go down. There are multiple
-  /// scenarios to consider:
-  /// - Setup for all scenarios:
-  ///   - Line 1 is `x1, y1` to `x2, y2`
-  ///   - Line 2 is `x3, y3` to `x4, y4`
-  ///   - General invariants:
-  ///     - Line orientation - both lines are oriented so that the start points
-  ///       are less than or equal to the end points.
-  ///       - `x1 <= x2 && y1 <= y2`
-  ///         - or `manhattan_distance(x1,y1) < manhattan_distance(x2,y2)`
-  ///         - in case of a tie, start point should be the lower `x` or `y`
-  ///           value.
-  ///       - `x3 <= x4 && y3 <= y4`
-  ///         - or `manhattan_distance(x3,y3) < manhattan_distance(x4,y4)`
-  ///     - Distance to zero - Line 1 is closer to the origin than line 2.
-  ///       - `manhattan_distance(x1,y1) <= manhattan_distance(x3,y3)`
-  /// - 2 `Horizontal` lines
-  ///   - Invariants:
-  ///     - `y1 == y2 && y3 == y4` (on horizontal lines, the `y` never changes)
-  ///     - `x1 <= x3`
-  ///       - if not, swap them so Line 1 is to the left of Line 2 and/or they
-  ///         start at the same point.
-  ///   - Scenarios:
-  ///     - The lines have no overlap at all.
-  ///       - `y1 != y3` (i.e. they are vertically offset from each other).
-  ///       - `x2 < x3` (i.e. Line 1 ends before Line 2 starts).
-  ///     - One line is completely within another one.
-  ///       - `x1 <= x3 && x2 >= x4` (Line 2 is shorter or they are the same
-  ///         length)
-  ///       - `x1 == x3 && x2 < x4` (Line 1 is shorter)
-  ///         - In both scenarios, the closest intersection point is at: `(x3,
-  ///           y1)`.  If Line 2 is shorter, the other intersection point is
-  ///           `(x4, y1)`.  If Line 1 is shorter, the other intersection point
-  ///           is `(x2, y1)`.  However, both of these are guaranteed to be
-  ///           farther away from the origin than `(x3, y1)` if the
-  ///           pre-conditions / invariants are true.
-  ///     - There is a partial overlap of the two lines
-  ///       - `x1 <= x3 && x2 < x4`
-  ///       - `x1 < x3 && x2 <= x4`
-  ///         - Intersect at: `(x3, y1)`.  All other intersection points, such
-  ///           as `(x2, y1)`, are guaranteed to be farther away from the origin
-  ///           point.
-  /// - 2 Vertical lines.
-  ///   - Similar to 2 Horizontal lines.
-  ///   - Invariants:
-  ///     - `x1 == x2 && x3 == x4` (on vertical lines, the `x` doesn't change
-  ///       for each line)
-  ///     - `y1 <= y3`
-  ///       - if not, swap them so Line 1 is above Line 2 and/or they start at
-  ///         the same point.
-  ///   - Scenarios:
-  ///     - The lines have no overlap at all.
-  ///       - `x1 != x3` (i.e. they are horizontally offset from each other).
-  ///       - `y2 < y3` (i.e. Line 1 ends before Line 2 starts)
-  ///     - One line is completely within another one.
-  ///       - `y1 <= y3 && y2>= y4` (Line 2 is shorter or they are the same
-  ///         length)
-  ///       - `y1 == y3 && y2 < y4` (Line 1 is shorter)
-  ///         - Both scenarios intersect at: `(x1, y3)`. Other intersection
-  ///           points, such as `(x1, y4)` or `(x1, y2)` are guaranteed to be
-  ///           farther away from the origin if the pre-conditions / invariants
-  ///           are true.
-  ///     - Partial overlap of the lines:
-  ///       - `y1 <= y3 && y2 < y4`
-  ///       - `y1 < y3 && y2 <= y4`
-  ///       - Intersect at: `min()`
-  /// - 1 Horizontal and 1 Vertical line
-  ///   - Line 1 is the horizontal line.
-  ///   - Line 2 is the vertical line.
-  ///   - Invariants:
-  ///     - `y1 == y2` (Line 1 is horizontal)
-  ///     - `x3 == x4` (Line 2 is vertical)
-  ///       - If these invariants are not true, swap the lines.
-  ///   - Scenarios:
-  ///     - There is no overlap.
-  ///       - Vertical offset.
-  ///         - `y1 < y3` (Line 1 is above Line 2)
-  ///         - `y1 > y4` (Line 1 is below Line 2)
-  ///       - Horizontal offset.
-  ///         - `x2 < x3` (Line 1 is to left of Line 2)
-  ///         - `x1 > x4` (Line 1 is to the right of Line 2)
-  ///     - There is one point of overlap.
-  ///       - Intersect at: `x3, y1`
-  ///
-  /// *NOTE:* Don't compare `x` and `y` values directly because if the numbers
-  /// are negative, then the operations are reversed.  We want distance to
-  /// origin `(0,0)` to determine which point is "smaller", not a pure
-  /// mathematical comparison.  E.g. mathematically, `-4 < -2`.  However, in
-  /// our problem, `-2` is considered the smaller value because it is closer
-  /// to `0`.
-  pub fn intersection(&self, other_point: &Point) -> Result<Point, &str> {
-    let (line1, line2) = (1, 2);
-    unimplemented!("intersection")
-  }
-}
-
-#[derive(Debug)]
+    Ok(Self { orientation: direction, start: sp, end: ep })
+  }
+
+  #[must_use]
+  pub fn get_orientation(&self) -> Orientation { self.orientation.clone() }
+
+  #[must_use]
+  pub const fn get_start(&self) -> Point { self.start }
+
+  #[must_use]
+  pub const fn get_end(&self) -> Point { self.end }
+
+  /// Whether `point` lies on this section, end points included.
+  #[must_use]
+  pub const fn contains(&self, point: &Point) -> bool {
+    on_segment(self.start, self.end, *point)
+  }
+
+  /// The line through this section as `(a, b, k)`, such that every point of
+  /// the line has `a * x + b * y == k`. Sections with the same orientation
+  /// share `a` and `b`, and are collinear if they also share `k`.
+  const fn line(&self) -> (i64, i64, i64) {
+    let (x, y) = (self.start.x as i64, self.start.y as i64);
+    match self.orientation {
+      Horizontal => (0, 1, y),
+      Vertical => (1, 0, x),
+      Diagonal => (-1, 1, y - x),
+      AntiDiagonal => (1, 1, x + y),
+    }
+  }
+
+  /// Calculate the intersection of two `WireSection`s. As context, my mental
+  /// plane under consideration has `(0,0)` at the top-left corner, `x`
+  /// increases to the right, and `y` increases as you go down. `create`
+  /// guarantees that the start of a section comes before its end when
+  /// comparing `Point`s, which is the order of the points along the section.
+  ///
+  /// - 2 sections with the same orientation
+  ///   - No overlap if they are not on the same line (their `line`s differ in
+  ///     `k`), or if one section ends before the other starts.
+  ///   - Otherwise they share the later of the two starts to the earlier of the
+  ///     two ends. This covers one section being completely within the other as
+  ///     well as a partial overlap. If the sections only touch end to end, the
+  ///     overlap is a single point.
+  /// - 2 sections with different orientations
+  ///   - Their lines meet at exactly one point, found by solving the two line
+  ///     equations.
+  ///   - No intersection if that point is not on both sections, or if it lies
+  ///     between grid points, as when two diagonals such as `(0,0)-(1,1)` and
+  ///     `(0,1)-(1,0)` pass each other at `(0.5,0.5)`. Wires only meet in the
+  ///     cells of the grid.
+  ///   - Otherwise they intersect at that point.
+  ///
+  /// Returns `None` if the sections do not meet. The result does not depend
+  /// on the order of the sections.
+  #[must_use]
+  pub fn intersection(&self, other: &Self) -> Option<Intersection> {
+    let (a1, b1, k1) = self.line();
+    let (a2, b2, k2) = other.line();
+    if self.orientation == other.orientation {
+      let start = self.start.max(other.start);
+      let end = self.end.min(other.end);
+      if k1 != k2 || start > end {
+        None
+      } else {
+        Some(Intersection::between(start, end))
+      }
+    } else {
+      // Cramer's rule. The orientations differ, so the determinant is not 0.
+      let determinant = a1 * b2 - a2 * b1;
+      let (x, y) = (k1 * b2 - k2 * b1, a1 * k2 - a2 * k1);
+      if x % determinant != 0 || y % determinant != 0 {
+        return None;
+      }
+      let crossing = Point::new(
+        i32::try_from(x / determinant).ok()?,
+        i32::try_from(y / determinant).ok()?,
+      );
+      if self.contains(&crossing) && other.contains(&crossing) {
+        Some(Intersection::Point(crossing))
+      } else {
+        None
+      }
+    }
+  }
+}
+
+/// A wire, made of the `WireSection`s it passes through in order.
+///
+/// `WireSection::create` orders the end points of each section, which loses
+/// the direction the wire was travelling in, so the wire also keeps its
+/// corners in the order they are visited and the number of steps taken to
+/// reach the start of each section.
+///
+/// With the `serde` feature, a wire is stored as its corners only, and
+/// rebuilt with `Wire::try_new` when it is read back.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Serialize, Deserialize),
+  serde(try_from = "Vec<Point>", into = "Vec<Point>")
+)]
 pub struct Wire {
   sections: Vec<WireSection>,
+  corners: Vec<Point>,
+  steps: Vec<i64>,
+}
+
+impl Wire {
+  /// Create a `Wire` that visits `corners` in order.
+  ///
+  /// # Panics
+  ///
+  /// Panics if two consecutive corners are identical or are not on the same
+  /// horizontal, vertical or diagonal line, like `WireSection::create`.
+  #[must_use]
+  pub fn new(corners: Vec<Point>) -> Self {
+    Self::try_new(corners).unwrap_or_else(|e| panic!("{}", e))
+  }
+
+  /// Create a `Wire` that visits `corners` in order, like `Wire::new`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if two consecutive corners are identical or are not on
+  /// the same horizontal, vertical or diagonal line, like
+  /// `WireSection::try_create`.
+  pub fn try_new(corners: Vec<Point>) -> Result<Self, String> {
+    let mut sections = Vec::with_capacity(corners.len().saturating_sub(1));
+    let mut steps = Vec::with_capacity(sections.capacity());
+    let mut total = 0;
+    for pair in corners.windows(2) {
+      sections.push(WireSection::try_create(pair[0], pair[1])?);
+      steps.push(total);
+      total += pair[0].steps_along(&pair[1]);
+    }
+    Ok(Self { sections, corners, steps })
+  }
+
+  /// Create a `Wire` by walking each `PathEntry` of `path` in turn, starting
+  /// at `start` rather than at the central port, such as a route found by
+  /// `route::route`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a corner does not fit in an `i32`, like `Point::walk`.
+  #[must_use]
+  pub fn starting_at(start: Point, path: &Path) -> Self {
+    Self::try_starting_at(start, path).unwrap_or_else(|e| panic!("{}", e))
+  }
+
+  /// Create a `Wire` by walking `path` from `start`, like `starting_at`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error naming the first entry of `path` that leads to a
+  /// corner that does not fit in an `i32`.
+  pub fn try_starting_at(start: Point, path: &Path) -> Result<Self, String> {
+    let mut corners = Vec::with_capacity(path.moves.len() + 1);
+    corners.push(start);
+    for (i, entry) in path.moves.iter().enumerate() {
+      let last = corners[corners.len() - 1];
+      corners.push(last.try_walk(entry).ok_or_else(|| {
+        format!(
+          "Entry {i} ({}{}) from ({}, {}) leaves the i32 grid",
+          entry.direction, entry.distance, last.x, last.y
+        )
+      })?);
+    }
+    Self::try_new(corners)
+  }
+
+  #[must_use]
+  pub fn get_sections(&self) -> &[WireSection] { &self.sections }
+
+  /// Absolute coordinates of the start of the wire, of every turn, and of the
+  /// end of the wire, in the order they are visited.
+  #[must_use]
+  pub fn get_corners(&self) -> &[Point] { &self.corners }
+
+  /// Number of steps taken to reach the start of each section, where the
+  /// start is the end the wire enters it from.
+  #[must_use]
+  pub fn get_step_counts(&self) -> &[i64] { &self.steps }
+
+  /// Total number of steps along the wire.
+  #[must_use]
+  pub fn length(&self) -> i64 {
+    self.corners.windows(2).map(|pair| pair[0].steps_along(&pair[1])).sum()
+  }
+
+  /// The smallest rectangle holding every corner of the wire, as
+  /// `(min_x, min_y, max_x, max_y)`, or `None` if it has no corners.
+  #[must_use]
+  pub fn bounding_box(&self) -> Option<(i32, i32, i32, i32)> {
+    let first = self.corners.first()?;
+    Some(self.corners.iter().fold(
+      (first.x, first.y, first.x, first.y),
+      |(min_x, min_y, max_x, max_y), p| {
+        (min_x.min(p.x), min_y.min(p.y), max_x.max(p.x), max_y.max(p.y))
+      },
+    ))
+  }
+
+  /// Number of times the wire changes direction, doubling back included.
+  /// Moves that carry on in the same direction, like `R3,R4`, do not turn.
+  #[must_use]
+  pub fn turns(&self) -> usize {
+    self
+      .corners
+      .windows(3)
+      .filter(|c| heading(c[0], c[1]) != heading(c[1], c[2]))
+      .count()
+  }
+
+  /// The longest straight stretch of the wire, where moves that carry on in
+  /// the same direction count as one, from where the wire enters it to where
+  /// it turns off. The first is picked if several are as long, and `None` if
+  /// the wire has no sections.
+  #[must_use]
+  pub fn longest_run(&self) -> Option<(Point, Point)> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for (i, c) in self.corners.windows(3).enumerate() {
+      if heading(c[0], c[1]) != heading(c[1], c[2]) {
+        runs.push((self.corners[start], c[1]));
+        start = i + 1;
+      }
+    }
+    if self.corners.len() > 1 {
+      runs.push((self.corners[start], self.corners[self.corners.len() - 1]));
+    }
+    runs.into_iter().reduce(|longest, run| {
+      if run.0.steps_along(&run.1) > longest.0.steps_along(&longest.1) {
+        run
+      } else {
+        longest
+      }
+    })
+  }
+
+  /// The point of the wire farthest from the central port, as measured by
+  /// `Frame::farthest_point`.
+  #[must_use]
+  pub fn farthest_point(&self) -> Option<Point> {
+    Frame::DEFAULT.farthest_point(self)
+  }
+
+  /// Fewest steps along the wire needed to reach `point`, or `None` if the
+  /// wire does not pass through it.
+  #[must_use]
+  pub fn steps_to(&self, point: &Point) -> Option<i64> {
+    self
+      .sections
+      .iter()
+      .zip(&self.corners)
+      .zip(&self.steps)
+      .find(|((section, _), _)| section.contains(point))
+      .map(|((_, entry), steps)| steps + entry.steps_along(point))
+  }
+
+  /// Whether every point of `section` is also a point of this wire.
+  ///
+  /// The points of `section` on this wire are its intersections with the
+  /// sections of this wire, measured in steps from its start. They cover the
+  /// whole section if, once sorted, each begins at most one step after the
+  /// ones before it end.
+  fn covers(&self, section: &WireSection) -> bool {
+    let along = |p: Point| section.start.steps_along(&p);
+    let mut spans: Vec<(i64, i64)> = self
+      .sections
+      .iter()
+      .filter_map(|s| section.intersection(s))
+      .map(|intersection| match intersection {
+        Intersection::Point(p) => (along(p), along(p)),
+        Intersection::Overlap(o) => (along(o.start), along(o.end)),
+      })
+      .collect();
+    spans.sort_unstable();
+    let mut covered = -1;
+    for (start, end) in spans {
+      if start > covered + 1 {
+        return false;
+      }
+      covered = covered.max(end);
+    }
+    covered == section.start.steps_along(&section.end)
+  }
+
+  /// Whether the two wires pass through the same points, whatever the order
+  /// or the number of times. Sections are compared with each other rather
+  /// than point by point, so long sections cost no more than short ones.
+  #[must_use]
+  pub fn same_points(&self, other: &Self) -> bool {
+    if self.sections.is_empty() || other.sections.is_empty() {
+      // A wire without sections is at most a single point, and any other wire
+      // has at least two.
+      self.sections.is_empty()
+        && other.sections.is_empty()
+        && self.corners == other.corners
+    } else {
+      self.sections.iter().all(|s| other.covers(s))
+        && other.sections.iter().all(|s| self.covers(s))
+    }
+  }
+
+  /// Whether the wire ends where it started, having gone somewhere.
+  #[must_use]
+  pub fn is_closed(&self) -> bool {
+    !self.sections.is_empty() && self.corners.first() == self.corners.last()
+  }
+
+  /// Every place where the wire meets itself, sorted and without repeats:
+  /// where two of its sections cross, touch or overlap, other than the corner
+  /// where a section joins the next one. A section that doubles back over the
+  /// one before it overlaps it, from the corner to where it turns off again.
+  ///
+  /// The start of a closed wire joins its last section to its first, so a
+  /// loop that goes around once without crossing itself has no
+  /// self-crossings. Every pair of sections is compared, which takes
+  /// `O(n²)` time for `n` sections.
+  #[must_use]
+  pub fn self_crossings(&self) -> Vec<Intersection> {
+    let last = self.sections.len().saturating_sub(1);
+    let joint = |i: usize, j: usize| {
+      if j == i + 1 {
+        Some(self.corners[j])
+      } else if i == 0 && j == last && self.is_closed() {
+        Some(self.corners[0])
+      } else {
+        None
+      }
+    };
+    let mut found = Vec::new();
+    for (i, a) in self.sections.iter().enumerate() {
+      for (j, b) in self.sections.iter().enumerate().skip(i + 1) {
+        match a.intersection(b) {
+          Some(Intersection::Point(p)) if joint(i, j) == Some(p) => (),
+          Some(intersection) => found.push(intersection),
+          None => (),
+        }
+      }
+    }
+    found.sort();
+    found.dedup();
+    found
+  }
+
+  /// The first point the wire comes back to after passing through it, with
+  /// the number of steps taken to get back there, or `None` if the wire
+  /// never goes anywhere twice. The central port counts as visited at the
+  /// start, so a closed wire comes back to it at the end if not before.
+  #[must_use]
+  pub fn first_revisit(&self) -> Option<(Point, i64)> {
+    for (j, section) in self.sections.iter().enumerate() {
+      let (from, to) = (self.corners[j], self.corners[j + 1]);
+      let revisits =
+        self.sections[..j].iter().enumerate().filter_map(|(i, earlier)| {
+          match earlier.intersection(section)? {
+            // Sections always meet the one before them where they join, so
+            // only doubling back over it is a revisit, starting at the first
+            // step.
+            Intersection::Point(_) if i + 1 == j => None,
+            Intersection::Overlap(_) if i + 1 == j => {
+              let (dx, dy) = heading(from, to);
+              Some(Point::new(from.x + dx, from.y + dy))
+            },
+            Intersection::Point(p) => Some(p),
+            Intersection::Overlap(o) =>
+              Some(min_by_key(o.start, o.end, |p| from.steps_along(p))),
+          }
+        });
+      if let Some(p) = revisits.min_by_key(|p| from.steps_along(p)) {
+        return Some((p, self.steps[j] + from.steps_along(&p)));
+      }
+    }
+    None
+  }
+
+  /// Twice the area enclosed by a closed wire, found with the shoelace
+  /// formula from its corners, or `None` if the wire is not closed.
+  ///
+  /// Diagonal sections can cut squares of the grid in half, so the area
+  /// itself may end in a half, while twice the area is always a whole
+  /// number. It is summed in `i128`, so it is exact for any corners. Where
+  /// the wire crosses itself, the parts it goes around in opposite
+  /// directions count against each other.
+  #[must_use]
+  pub fn twice_enclosed_area(&self) -> Option<u128> {
+    self.is_closed().then(|| {
+      let twice: i128 = self
+        .corners
+        .windows(2)
+        .map(|pair| {
+          let (p, q) = (pair[0], pair[1]);
+          i128::from(p.x) * i128::from(q.y) - i128::from(q.x) * i128::from(p.y)
+        })
+        .sum();
+      twice.unsigned_abs()
+    })
+  }
+
+  /// Every place where a section of this wire meets a section of `other`,
+  /// including the central port if both wires start there.
+  #[must_use]
+  pub fn crossings(&self, other: &Self) -> Vec<Intersection> {
+    self
+      .sections
+      .iter()
+      .flat_map(|a| {
+        other.sections.iter().filter_map(move |b| a.intersection(b))
+      })
+      .collect()
+  }
+}
+
+impl TryFrom<&Path> for Wire {
+  type Error = String;
+
+  /// Walks each `PathEntry` in turn, starting at `Point::ORIGIN`. Fails if a
+  /// corner does not fit in an `i32`, as `Wire::try_starting_at` does.
+  fn try_from(path: &Path) -> Result<Self, Self::Error> {
+    Self::try_starting_at(Point::ORIGIN, path)
+  }
+}
+
+impl From<WireSection> for [Point; 2] {
+  fn from(section: WireSection) -> Self { [section.start, section.end] }
+}
+
+impl TryFrom<[Point; 2]> for WireSection {
+  type Error = String;
+
+  fn try_from([point1, point2]: [Point; 2]) -> Result<Self, Self::Error> {
+    Self::try_create(point1, point2)
+  }
+}
+
+impl From<Wire> for Vec<Point> {
+  fn from(wire: Wire) -> Self { wire.corners }
+}
+
+impl TryFrom<Vec<Point>> for Wire {
+  type Error = String;
+
+  fn try_from(corners: Vec<Point>) -> Result<Self, Self::Error> {
+    Self::try_new(corners)
+  }
+}
+
+// =============================================================================
+// Section for solving
+// =============================================================================
+
+/// The points of `intersection` where a solver can find its best crossing,
+/// leaving out the central port.
+///
+/// Along an overlap, the distance to the origin of `frame` is smallest at the
+/// point closest to it by `metric`, and the steps taken by each wire change by
+/// one per point, so their sum is smallest at one of the ends. Points reached
+/// earlier through another section of a wire are also the intersection of that
+/// section with the other wire, so they are candidates in their own right.
+/// If the overlap contains the central port, its neighbours stand in for it.
+fn candidates(
+  intersection: &Intersection,
+  frame: Frame,
+  metric: &dyn Metric,
+) -> Vec<Point> {
+  let origin = frame.get_origin();
+  let mut points = match *intersection {
+    Intersection::Point(p) => vec![p],
+    Intersection::Overlap(overlap) => {
+      let mut points =
+        vec![overlap.start, overlap.end, overlap.closest_by(origin, metric)];
+      if overlap.contains(&Point::ORIGIN) {
+        points.extend(
+          [
+            (1, 0),
+            (-1, 0),
+            (0, 1),
+            (0, -1),
+            (1, 1),
+            (-1, -1),
+            (1, -1),
+            (-1, 1),
+          ]
+          .iter()
+          .map(|&(x, y)| Point::new(x, y))
+          .filter(|p| overlap.contains(p)),
+        );
+      }
+      points
+    },
+  };
+  points.retain(|p| *p != Point::ORIGIN);
+  points
+}
+
+/// Every crossing of two wires that a solver needs to consider, found with
+/// `sweep::crossings`.
+fn crossing_candidates(
+  wire1: &Wire,
+  wire2: &Wire,
+  frame: Frame,
+  metric: &dyn Metric,
+) -> Vec<Point> {
+  sweep::crossings(wire1, wire2)
+    .iter()
+    .flat_map(|intersection| candidates(intersection, frame, metric))
+    .collect()
+}
+
+impl Frame {
+  /// The point of `wire` farthest from the origin of this frame, preferring
+  /// the one the wire reaches first, or `None` if it has no corners.
+  ///
+  /// Along a straight section, the distance keeps falling and then rising,
+  /// if it changes direction at all, so the farthest point is always a
+  /// corner.
+  #[must_use]
+  pub fn farthest_point(&self, wire: &Wire) -> Option<Point> {
+    wire.corners.iter().copied().reduce(|farthest, p| {
+      if self.distance(&p) > self.distance(&farthest) { p } else { farthest }
+    })
+  }
+
+  /// The crossing of two wires closest to the origin of this frame, ignoring
+  /// the central port where both wires start.
+  #[must_use]
+  pub fn closest_crossing(&self, wire1: &Wire, wire2: &Wire) -> Option<Point> {
+    self.closest_crossing_by(wire1, wire2, &Manhattan)
+  }
+
+  /// The crossing of two wires closest to the origin of this frame by
+  /// `metric`, ignoring the central port where both wires start.
+  #[must_use]
+  pub fn closest_crossing_by(
+    &self,
+    wire1: &Wire,
+    wire2: &Wire,
+    metric: &dyn Metric,
+  ) -> Option<Point> {
+    crossing_candidates(wire1, wire2, *self, metric)
+      .into_iter()
+      .reduce(|closest, p| *metric.closer(self.origin, &closest, &p))
+  }
+}
+
+/// The crossing of two wires closest to the central port, ignoring the
+/// central port itself where both wires start.
+#[must_use]
+pub fn closest_crossing(wire1: &Wire, wire2: &Wire) -> Option<Point> {
+  Frame::DEFAULT.closest_crossing(wire1, wire2)
+}
+
+/// The crossing of two wires that the wires reach in the fewest combined
+/// steps, ignoring the central port, and that number of steps. Each wire is
+/// counted up to the first time it reaches the crossing.
+#[must_use]
+pub fn fewest_steps_crossing(
+  wire1: &Wire,
+  wire2: &Wire,
+) -> Option<(Point, i64)> {
+  let (index1, index2) = (SectionIndex::new(wire1), SectionIndex::new(wire2));
+  // Only the closest point of an overlap depends on the metric, and it does
+  // not matter here.
+  crossing_candidates(wire1, wire2, Frame::DEFAULT, &Manhattan)
+    .into_iter()
+    .filter_map(|p| Some((p, index1.steps_to(&p)? + index2.steps_to(&p)?)))
+    .min_by_key(|&(_, steps)| steps)
+}
+
+/// Every segment along which the two wires run together, in order, each
+/// once.
+#[must_use]
+pub fn overlaps(wire1: &Wire, wire2: &Wire) -> Vec<Overlap> {
+  let mut overlaps: Vec<Overlap> = sweep::crossings(wire1, wire2)
+    .into_iter()
+    .filter_map(|intersection| match intersection {
+      Intersection::Overlap(overlap) => Some(overlap),
+      Intersection::Point(_) => None,
+    })
+    .collect();
+  overlaps.sort_unstable();
+  overlaps.dedup();
+  overlaps
+}
+
+/// Every integer point on the overlaps of the two wires, in order, each once,
+/// ignoring the central port where both wires start.
+#[must_use]
+pub fn overlap_points(wire1: &Wire, wire2: &Wire) -> Vec<Point> {
+  let points: BTreeSet<Point> = overlaps(wire1, wire2)
+    .iter()
+    .flat_map(Overlap::points)
+    .filter(|p| *p != Point::ORIGIN)
+    .collect();
+  points.into_iter().collect()
+}
+
+/// What the solver minimises when picking a crossing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SolverMode {
+  /// Manhattan distance from the central port (Problem 3a), or another
+  /// `Metric` given to `solve_by`.
+  ClosestDistance,
+  /// Combined steps taken by both wires (Problem 3b).
+  FewestSteps,
+}
+
+impl FromStr for SolverMode {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "distance" => Ok(Self::ClosestDistance),
+      "steps" => Ok(Self::FewestSteps),
+      _ => Err(format!("Not one of distance, steps: {s}")),
+    }
+  }
+}
+
+/// Parse one `Path` from each non-empty line of `s`.
+///
+/// # Errors
+///
+/// Returns a description of every error on every line that is not a valid
+/// `Path`, each with its line number, counting from 1, and the line with the
+/// bad part underlined. A path that, walked from the central port, leaves the
+/// `i32` grid is also an error.
+pub fn parse_paths(s: &str) -> Result<Vec<Path>, String> {
+  let mut paths = Vec::new();
+  let mut messages = Vec::new();
+  for (i, line) in s.lines().enumerate() {
+    if line.trim().is_empty() {
+      continue;
+    }
+    match Path::try_new(line) {
+      Ok(path) => match Wire::try_from(&path) {
+        Ok(_) => paths.push(path),
+        Err(e) => messages.push(format!("Line {}: {e}", i + 1)),
+      },
+      Err(errors) =>
+        for e in errors {
+          messages.push(format!("Line {}: {e}\n{}", i + 1, e.underline(line)));
+        },
+    }
+  }
+  if messages.is_empty() { Ok(paths) } else { Err(messages.join("\n")) }
+}
+
+/// Parse the puzzle input: two `Path`s, one per line.
+///
+/// # Errors
+///
+/// Returns an error if a line is not a valid `Path`, as described by
+/// `parse_paths`, or if there are not exactly two non-empty lines.
+pub fn parse_input(s: &str) -> Result<(Path, Path), String> {
+  <[Path; 2]>::try_from(parse_paths(s)?)
+    .map(Into::into)
+    .map_err(|paths| format!("Expected 2 paths, found {}", paths.len()))
+}
+
+/// Solve the puzzle for `input` in the given mode, returning the smallest
+/// Manhattan distance or number of steps.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be parsed or the wires never cross.
+pub fn solve(input: &str, mode: SolverMode) -> Result<i64, String> {
+  let answer = solve_by(input, mode, &Manhattan)?;
+  i64::try_from(answer).map_err(|e| e.to_string())
+}
+
+/// Solve the puzzle for `input` in the given mode, ranking crossings by
+/// `metric` in `SolverMode::ClosestDistance`, and returning the smallest
+/// distance by that metric or number of steps.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be parsed or the wires never cross.
+pub fn solve_by(
+  input: &str,
+  mode: SolverMode,
+  metric: &dyn Metric,
+) -> Result<i128, String> {
+  let (path1, path2) = parse_input(input)?;
+  let (wire1, wire2) = (Wire::try_from(&path1)?, Wire::try_from(&path2)?);
+  match mode {
+    SolverMode::ClosestDistance => Frame::DEFAULT
+      .closest_crossing_by(&wire1, &wire2, metric)
+      .map(|p| metric.distance(Point::ORIGIN, p)),
+    SolverMode::FewestSteps =>
+      fewest_steps_crossing(&wire1, &wire2).map(|(_, steps)| i128::from(steps)),
+  }
+  .ok_or_else(|| "The wires do not cross".into())
+}
+
+/// The executor for Problem 3a: the Manhattan distance from the central port
+/// to the closest crossing.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be parsed or the wires never cross.
+pub fn problem_3a(input: &str) -> Result<i64, String> {
+  solve(input, SolverMode::ClosestDistance)
+}
+
+/// The executor for Problem 3b: the fewest combined steps the wires take to
+/// reach a crossing.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be parsed or the wires never cross.
+pub fn problem_3b(input: &str) -> Result<i64, String> {
+  solve(input, SolverMode::FewestSteps)
 }
@@ -0,0 +1,154 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+use std::convert::TryFrom;
+
+use crate::index::SectionIndex;
+use crate::problem_3a::{
+  MovementDirection, Orientation, Path, PathEntry, Point, Wire,
+};
+
+/// A point of the compressed grid, as its indices into the critical `x` and
+/// `y` coordinates.
+type Node = (usize, usize);
+
+/// The coordinates, picked out of each point by `coordinate`, that a
+/// shortest route may need to stop or turn at: those of `ends`, those of
+/// every corner of the wires and the coordinates either side of them, and
+/// every coordinate that a diagonal section passes through, and the ones
+/// either side of it. Sorted, without repeats, and leaving out any that do
+/// not fit in an `i32`.
+///
+/// Between two consecutive critical coordinates, every line across the grid
+/// meets the wires in the same way, so a route never needs to turn there.
+fn critical(
+  wires: &[Wire],
+  ends: [Point; 2],
+  coordinate: fn(&Point) -> i32,
+) -> Vec<i32> {
+  let mut found: BTreeSet<i64> =
+    ends.iter().map(|p| i64::from(coordinate(p))).collect();
+  for wire in wires {
+    for corner in wire.get_corners() {
+      let c = i64::from(coordinate(corner));
+      found.extend(c - 1..=c + 1);
+    }
+    for section in wire.get_sections() {
+      if let Orientation::Diagonal | Orientation::AntiDiagonal =
+        section.get_orientation()
+      {
+        let c1 = i64::from(coordinate(&section.get_start()));
+        let c2 = i64::from(coordinate(&section.get_end()));
+        found.extend(c1.min(c2) - 1..=c1.max(c2) + 1);
+      }
+    }
+  }
+  found.into_iter().filter_map(|c| i32::try_from(c).ok()).collect()
+}
+
+/// The moves going straight from `from` to `to`, on the same horizontal or
+/// vertical line, split where the distance does not fit in an `i32`.
+fn moves_between(from: Point, to: Point) -> Vec<PathEntry> {
+  let direction =
+    match (to.get_x().cmp(&from.get_x()), to.get_y().cmp(&from.get_y())) {
+      (Ordering::Greater, _) => MovementDirection::R,
+      (Ordering::Less, _) => MovementDirection::L,
+      (_, Ordering::Greater) => MovementDirection::D,
+      _ => MovementDirection::U,
+    };
+  let mut moves = Vec::with_capacity(1);
+  let mut left = from.steps_to(&to);
+  while left > 0 {
+    let distance = left.min(i64::from(i32::MAX));
+    left -= distance;
+    if let Ok(distance) = i32::try_from(distance) {
+      moves.push(PathEntry::new(direction.clone(), distance));
+    }
+  }
+  moves
+}
+
+/// The shortest route from `start` to `goal` that never crosses or touches
+/// any of `wires`.
+///
+/// The route moves only horizontally and vertically, and comes back as a
+/// `Path` of moves starting at `start`, which `Wire::starting_at` turns into
+/// a wire.
+///
+/// Returns `None` if there is no such route, which includes when `start` or
+/// `goal` is on one of the wires. Every wire built from a `Path` passes
+/// through the central port, so no route can start or end there.
+///
+/// The search is A* with Manhattan distance to `goal` as the estimate, over
+/// a grid of the critical coordinates only, where moving between neighbours
+/// costs the distance between them. Diagonal sections add every coordinate
+/// they pass through, so long ones make the grid large.
+#[must_use]
+pub fn route(wires: &[Wire], start: Point, goal: Point) -> Option<Path> {
+  let indices: Vec<SectionIndex> =
+    wires.iter().map(SectionIndex::new).collect();
+  let free =
+    |p: Point| indices.iter().all(|index| index.sections_at(p).is_empty());
+  if !free(start) || !free(goal) {
+    return None;
+  }
+
+  let xs = critical(wires, [start, goal], Point::get_x);
+  let ys = critical(wires, [start, goal], Point::get_y);
+  let point = |(i, j): Node| Point::new(xs[i], ys[j]);
+  let node = |p: Point| -> Option<Node> {
+    Some((
+      xs.binary_search(&p.get_x()).ok()?,
+      ys.binary_search(&p.get_y()).ok()?,
+    ))
+  };
+  let (from, to) = (node(start)?, node(goal)?);
+
+  let mut best: BTreeMap<Node, i64> = BTreeMap::new();
+  let mut came_from: BTreeMap<Node, Node> = BTreeMap::new();
+  let mut queue = BinaryHeap::new();
+  best.insert(from, 0);
+  queue.push(Reverse((start.steps_to(&goal), 0, from)));
+  while let Some(Reverse((_, steps, current))) = queue.pop() {
+    if current == to {
+      break;
+    }
+    if best.get(&current).is_some_and(|&b| steps > b) {
+      continue;
+    }
+    let (i, j) = current;
+    let neighbours = [
+      i.checked_sub(1).map(|i| (i, j)),
+      Some((i + 1, j)).filter(|&(i, _)| i < xs.len()),
+      j.checked_sub(1).map(|j| (i, j)),
+      Some((i, j + 1)).filter(|&(_, j)| j < ys.len()),
+    ];
+    for next in neighbours.iter().flatten() {
+      let p = point(*next);
+      if !free(p) {
+        continue;
+      }
+      let next_steps = steps + point(current).steps_to(&p);
+      if best.get(next).is_none_or(|&b| next_steps < b) {
+        best.insert(*next, next_steps);
+        came_from.insert(*next, current);
+        queue.push(Reverse((
+          next_steps + p.steps_to(&goal),
+          next_steps,
+          *next,
+        )));
+      }
+    }
+  }
+
+  best.get(&to)?;
+  let mut nodes = vec![to];
+  while let Some(previous) = came_from.get(&nodes[nodes.len() - 1]) {
+    nodes.push(*previous);
+  }
+  let moves = nodes
+    .windows(2)
+    .rev()
+    .flat_map(|pair| moves_between(point(pair[1]), point(pair[0])))
+    .collect();
+  Some(Path::new_internal(moves).normalise())
+}
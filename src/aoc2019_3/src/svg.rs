@@ -0,0 +1,190 @@
+use crate::board::Board;
+use crate::problem_3a::{Frame, Intersection, Overlap, Point};
+
+/// Colours given to the wires in turn, chosen to stay apart from each other
+/// and from the black and red markers.
+pub const PALETTE: [&str; 8] = [
+  "#1f77b4", "#2ca02c", "#ff7f0e", "#9467bd", "#17becf", "#8c564b", "#e377c2",
+  "#bcbd22",
+];
+
+/// The colour of wire number `i`.
+#[must_use]
+pub const fn colour(i: usize) -> &'static str { PALETTE[i % PALETTE.len()] }
+
+/// One SVG element on its own line, closed immediately if it has no
+/// `content`.
+fn element(name: &str, attributes: &[(&str, String)], content: &str) -> String {
+  let attributes: Vec<String> =
+    attributes.iter().map(|(k, v)| format!("{k}=\"{v}\"")).collect();
+  let attributes = attributes.join(" ");
+  if content.is_empty() {
+    format!("<{name} {attributes}/>\n")
+  } else {
+    format!("<{name} {attributes}>{content}</{name}>\n")
+  }
+}
+
+/// `value` to three decimal places, without trailing zeros, so that sizes
+/// come out as `0.3` rather than `0.30000000000000004`.
+fn number(value: f64) -> String {
+  let s = format!("{value:.3}");
+  let s = s.trim_end_matches('0').trim_end_matches('.');
+  if s == "-0" { "0".into() } else { s.into() }
+}
+
+/// A tooltip for the element it is placed in.
+fn title(text: &str) -> String { format!("<title>{text}</title>") }
+
+/// A thick black line along `overlap`, shared by `wires`.
+fn overlap_line(overlap: &Overlap, wires: &[usize], unit: f64) -> String {
+  let (start, end) = (overlap.get_start(), overlap.get_end());
+  element(
+    "line",
+    &[
+      ("x1", start.get_x().to_string()),
+      ("y1", start.get_y().to_string()),
+      ("x2", end.get_x().to_string()),
+      ("y2", end.get_y().to_string()),
+      ("stroke", "black".into()),
+      ("stroke-width", number(2.0 * unit)),
+      ("stroke-linecap", "round".into()),
+    ],
+    &title(&format!(
+      "Overlap ({}, {}) to ({}, {}): wires {:?}",
+      start.get_x(),
+      start.get_y(),
+      end.get_x(),
+      end.get_y(),
+      wires
+    )),
+  )
+}
+
+/// A black dot at `p`, where `wires` meet.
+fn crossing_dot(p: Point, wires: &[usize], unit: f64) -> String {
+  element(
+    "circle",
+    &[
+      ("cx", p.get_x().to_string()),
+      ("cy", p.get_y().to_string()),
+      ("r", number(unit)),
+      ("fill", "black".into()),
+    ],
+    &title(&format!("({}, {}): wires {:?}", p.get_x(), p.get_y(), wires)),
+  )
+}
+
+/// Draw the wires of `board` as an SVG image.
+///
+/// Each wire is a polyline in its own colour from `PALETTE`, in the order
+/// of the board. Where wires run together, the overlap is drawn over them as
+/// one thick black line, and every other point where wires meet is marked
+/// with a black dot. The crossing closest to the origin of `frame` is ringed
+/// in red, and the central port is marked and labelled. Grid coordinates are
+/// used as they are, since `y` increases downwards in SVG as well, and the view
+/// box is the bounding box of the wires with a margin around it.
+#[must_use]
+pub fn render(board: &Board, frame: Frame) -> String {
+  let (min_x, min_y, max_x, max_y) = board.bounding_box();
+  let width = f64::from(max_x) - f64::from(min_x);
+  let height = f64::from(max_y) - f64::from(min_y);
+  // Everything is sized relative to the drawing, so that tiny examples and
+  // full puzzle inputs both stay legible.
+  let unit = (width.max(height) / 100.0).max(0.1);
+  let margin = 4.0 * unit;
+  let padding = 2.0 * margin;
+
+  let mut wires = String::new();
+  for (i, wire) in board.get_wires().iter().enumerate() {
+    let points: Vec<String> = wire
+      .get_corners()
+      .iter()
+      .map(|p| format!("{},{}", p.get_x(), p.get_y()))
+      .collect();
+    wires += &element(
+      "polyline",
+      &[("stroke", colour(i).into()), ("points", points.join(" "))],
+      &title(&format!("Wire {i}")),
+    );
+  }
+
+  let mut svg = format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+    number(f64::from(min_x) - margin),
+    number(f64::from(min_y) - margin),
+    number(width + padding),
+    number(height + padding),
+  );
+  svg += &element(
+    "g",
+    &[
+      ("fill", "none".into()),
+      ("stroke-width", number(unit)),
+      ("stroke-linejoin", "round".into()),
+    ],
+    &format!("\n{wires}"),
+  );
+
+  // Each overlap is one line, so that its length does not matter.
+  let mut closest: Option<Point> = None;
+  for (intersection, wires) in board.meeting_points(2) {
+    let p = match intersection {
+      Intersection::Point(p) => {
+        svg += &crossing_dot(p, &wires, unit);
+        p
+      },
+      Intersection::Overlap(overlap) => {
+        svg += &overlap_line(&overlap, &wires, unit);
+        overlap.closest_to(frame.get_origin())
+      },
+    };
+    closest = Some(closest.map_or(p, |closest| *frame.closer(&closest, &p)));
+  }
+  if let Some(p) = closest {
+    svg += &element(
+      "circle",
+      &[
+        ("cx", p.get_x().to_string()),
+        ("cy", p.get_y().to_string()),
+        ("r", number(3.0 * unit)),
+        ("fill", "none".into()),
+        ("stroke", "red".into()),
+        ("stroke-width", number(unit)),
+      ],
+      &title(&format!(
+        "Closest crossing ({}, {}), distance {}",
+        p.get_x(),
+        p.get_y(),
+        frame.distance(&p)
+      )),
+    );
+  }
+
+  let (x, y) =
+    (f64::from(Point::ORIGIN.get_x()), f64::from(Point::ORIGIN.get_y()));
+  let offset = 1.5 * unit;
+  svg += &element(
+    "rect",
+    &[
+      ("x", number(x - unit)),
+      ("y", number(y - unit)),
+      ("width", number(2.0 * unit)),
+      ("height", number(2.0 * unit)),
+      ("fill", "black".into()),
+    ],
+    "",
+  );
+  svg += &element(
+    "text",
+    &[
+      ("x", number(x + offset)),
+      ("y", number(y - offset)),
+      ("font-size", number(3.0 * unit)),
+      ("font-family", "sans-serif".into()),
+    ],
+    "origin",
+  );
+  svg.push_str("</svg>\n");
+  svg
+}
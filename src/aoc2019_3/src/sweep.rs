@@ -0,0 +1,158 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::problem_3a::{Intersection, Orientation, Wire, WireSection};
+
+/// Which wire a section belongs to.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Side {
+  First,
+  Second,
+}
+
+/// What happens to a horizontal section, or a vertical one, at some `x`.
+/// The order of the variants is the order in which events at the same `x`
+/// are handled, so that sections touching at their ends are still found.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Event {
+  /// A horizontal section starts and becomes active.
+  Start,
+  /// A vertical section is compared with the active horizontal sections.
+  Query,
+  /// A horizontal section ends.
+  End,
+}
+
+/// Every crossing of a horizontal section with a vertical one from the other
+/// wire.
+///
+/// Events are sorted by `x`. Active horizontal sections are kept in an
+/// ordered set by `y`, so each vertical section only visits the horizontal
+/// sections it crosses.
+fn perpendicular(
+  sections: &[(Side, &WireSection)],
+  crossings: &mut Vec<Intersection>,
+) {
+  let mut events: Vec<(i32, Event, usize)> = vec![];
+  for (i, (_, section)) in sections.iter().enumerate() {
+    let (start, end) = (section.get_start(), section.get_end());
+    match section.get_orientation() {
+      Orientation::Horizontal => {
+        events.push((start.get_x(), Event::Start, i));
+        events.push((end.get_x(), Event::End, i));
+      },
+      Orientation::Vertical => events.push((start.get_x(), Event::Query, i)),
+      Orientation::Diagonal | Orientation::AntiDiagonal => (),
+    }
+  }
+  events.sort_unstable();
+
+  let mut active: BTreeSet<(i32, usize)> = BTreeSet::new();
+  for (_, event, i) in events {
+    let (side, section) = sections[i];
+    match event {
+      Event::Start => {
+        active.insert((section.get_start().get_y(), i));
+      },
+      Event::End => {
+        active.remove(&(section.get_start().get_y(), i));
+      },
+      Event::Query => {
+        let range = (section.get_start().get_y(), 0)
+          ..=(section.get_end().get_y(), usize::MAX);
+        for &(_, j) in active.range(range) {
+          let (other_side, other) = sections[j];
+          if other_side != side {
+            crossings.extend(other.intersection(section));
+          }
+        }
+      },
+    }
+  }
+}
+
+/// Where a section starts and ends along its line, and its index.
+type Span = (i32, i32, usize);
+
+/// Every overlap of collinear horizontal or vertical sections from different
+/// wires. Sections are grouped by the line they lie on and swept along it,
+/// keeping the active sections of each wire ordered by where they end.
+fn collinear(
+  sections: &[(Side, &WireSection)],
+  crossings: &mut Vec<Intersection>,
+) {
+  // Lines are keyed by (is vertical, fixed coordinate).
+  let mut lines: BTreeMap<(bool, i32), Vec<Span>> = BTreeMap::new();
+  for (i, (_, section)) in sections.iter().enumerate() {
+    let (start, end) = (section.get_start(), section.get_end());
+    let key = match section.get_orientation() {
+      Orientation::Horizontal =>
+        ((false, start.get_y()), (start.get_x(), end.get_x())),
+      Orientation::Vertical =>
+        ((true, start.get_x()), (start.get_y(), end.get_y())),
+      Orientation::Diagonal | Orientation::AntiDiagonal => continue,
+    };
+    lines.entry(key.0).or_default().push((key.1.0, key.1.1, i));
+  }
+
+  for line in lines.values_mut() {
+    line.sort_unstable();
+    let mut active: [BTreeSet<(i32, usize)>; 2] = Default::default();
+    for &(start, end, i) in line.iter() {
+      let (side, section) = sections[i];
+      let other = &mut active[usize::from(side == Side::First)];
+      // Sections of the other wire that end before this one starts can never
+      // overlap a later section either.
+      *other = other.split_off(&(start, 0));
+      for &(_, j) in other.iter() {
+        crossings.extend(sections[j].1.intersection(section));
+      }
+      active[usize::from(side == Side::Second)].insert((end, i));
+    }
+  }
+}
+
+/// Every meeting of a diagonal section with a section of the other wire,
+/// found by comparing them directly. Pairs of diagonal sections are only
+/// compared from the side of the first wire, so that each is found once.
+fn diagonal(
+  sections: &[(Side, &WireSection)],
+  crossings: &mut Vec<Intersection>,
+) {
+  let is_diagonal = |section: &WireSection| {
+    matches!(
+      section.get_orientation(),
+      Orientation::Diagonal | Orientation::AntiDiagonal
+    )
+  };
+  for &(side, section) in sections.iter().filter(|(_, s)| is_diagonal(s)) {
+    for &(other_side, other) in sections {
+      if other_side != side && (side == Side::First || !is_diagonal(other)) {
+        crossings.extend(section.intersection(other));
+      }
+    }
+  }
+}
+
+/// Every place where a section of `wire1` meets a section of `wire2`: the
+/// same intersections as `Wire::crossings`, in no particular order.
+///
+/// `Wire::crossings` compares every pair of sections, which takes `O(n·m)`
+/// time. This takes `O((n + m) log(n + m) + k)`, where `k` is the number of
+/// pairs of sections that meet, counting pairs from the same wire. Diagonal
+/// sections are left out of the sweep and compared with every section of the
+/// other wire instead, which adds `O(d·(n + m))` for `d` diagonal sections.
+#[must_use]
+pub fn crossings(wire1: &Wire, wire2: &Wire) -> Vec<Intersection> {
+  let sections: Vec<(Side, &WireSection)> = wire1
+    .get_sections()
+    .iter()
+    .map(|s| (Side::First, s))
+    .chain(wire2.get_sections().iter().map(|s| (Side::Second, s)))
+    .collect();
+
+  let mut crossings = vec![];
+  perpendicular(&sections, &mut crossings);
+  collinear(&sections, &mut crossings);
+  diagonal(&sections, &mut crossings);
+  crossings
+}
@@ -0,0 +1,78 @@
+//! Strategies and helpers shared by the test files.
+
+// Each test file is its own crate and uses only some of these.
+#![allow(dead_code)]
+
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+
+/// Generate `MovementDirection` values
+pub fn strategy_movement_direction() -> impl Strategy<Value = MovementDirection>
+{
+  prop_oneof![
+    Just(MovementDirection::U),
+    Just(MovementDirection::D),
+    Just(MovementDirection::L),
+    Just(MovementDirection::R),
+  ]
+}
+
+prop_compose! {
+  /// Generate `PathEntry` values of fewer than `max_distance` steps
+  pub fn arb_pathentry(max_distance: i32)
+                      (direction in strategy_movement_direction(),
+                       distance in 1..max_distance)
+                      -> PathEntry {
+    PathEntry::new(direction, distance)
+  }
+}
+
+prop_compose! {
+  /// Generate `Path` values with fewer than `max_segments` entries of fewer
+  /// than `max_distance` steps each
+  pub fn arb_path(max_segments: usize, max_distance: i32)
+                 (vec in prop::collection::vec(arb_pathentry(max_distance),
+                                               1..max_segments))
+                 -> Path {
+    Path::new_internal(vec)
+  }
+}
+
+/// Generate `MovementDirection` values, diagonals included
+pub fn strategy_eight_way_direction() -> impl Strategy<Value = MovementDirection>
+{
+  prop_oneof![
+    strategy_movement_direction(),
+    Just(MovementDirection::UL),
+    Just(MovementDirection::UR),
+    Just(MovementDirection::DL),
+    Just(MovementDirection::DR),
+  ]
+}
+
+prop_compose! {
+  /// Generate `Path` values that may move diagonally, with fewer than
+  /// `max_segments` entries of fewer than `max_distance` steps each. Short
+  /// entries make wires cross and overlap often.
+  pub fn arb_diagonal_path(max_segments: usize, max_distance: i32)
+                          (vec in prop::collection::vec(
+                            (strategy_eight_way_direction(), 1..max_distance)
+                              .prop_map(|(d, n)| PathEntry::new(d, n)),
+                            1..max_segments))
+                          -> Path {
+    Path::new_internal(vec)
+  }
+}
+
+/// Every point `path` arrives at from `start`, one step at a time, starting
+/// with `start` itself.
+pub fn walk(start: Point, path: &Path) -> Vec<Point> {
+  let mut points = vec![start];
+  for entry in &path.moves {
+    let step = PathEntry::new(entry.get_direction(), 1);
+    for _ in 0..entry.get_distance() {
+      points.push(points[points.len() - 1].walk(&step));
+    }
+  }
+  points
+}
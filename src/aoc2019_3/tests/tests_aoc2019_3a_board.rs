@@ -0,0 +1,176 @@
+use aoc2019_3::board::Board;
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+mod common;
+use common::*;
+
+/// Three wires: 0 and 1 from the first example, and 2 running through both
+/// of their crossings.
+const THREE_WIRES: &str = "R8,U5,L5,D3\nU7,R6,D4,L4\nU3,R3,U2,R3";
+
+#[test]
+fn board_simple_parse() {
+  let board = THREE_WIRES.parse::<Board>().unwrap();
+  assert_eq!(board.get_wires().len(), 3);
+  assert_eq!(
+    board.get_wires()[0],
+    Wire::try_from(&Path::new("R8,U5,L5,D3")).unwrap()
+  );
+  assert!("R8\nX".parse::<Board>().is_err());
+  assert!("R8\nR2147483647,R1".parse::<Board>().is_err());
+  assert_eq!("".parse::<Board>().unwrap().get_wires().len(), 0);
+}
+
+#[test]
+fn board_simple_bounding_box() {
+  let board = |s: &str| s.parse::<Board>().unwrap();
+  assert_eq!(board("R8,U5,L5,D3\nU7,R6,D4,L4").bounding_box(), (0, -7, 8, 0));
+  assert_eq!(board("L3,D2").bounding_box(), (-3, 0, 0, 2));
+  assert_eq!(board("").bounding_box(), (0, 0, 0, 0));
+}
+
+#[test]
+fn board_simple_meeting_points() {
+  let board = THREE_WIRES.parse::<Board>().unwrap();
+  assert_eq!(board.meeting_points(3), vec![
+    (Intersection::Point(Point::new(3, -3)), vec![0, 1, 2]),
+    (Intersection::Point(Point::new(6, -5)), vec![0, 1, 2]),
+  ]);
+  let pairs = board.meeting_points(2);
+  assert!(pairs.contains(&(
+    Intersection::Overlap(Overlap::new(Point::new(0, -3), Point::new(0, -1))),
+    vec![1, 2]
+  )));
+  assert!(pairs.iter().all(|(i, _)| !i.points().contains(&Point::ORIGIN)));
+}
+
+#[test]
+fn board_simple_overlap_points() {
+  let board = "R5\nR3\nL2,R4".parse::<Board>().unwrap();
+  assert_eq!(board.meeting_points(2), vec![
+    (
+      Intersection::Overlap(Overlap::new(Point::new(1, 0), Point::new(2, 0))),
+      vec![0, 1, 2]
+    ),
+    (Intersection::Point(Point::new(3, 0)), vec![0, 1]),
+  ]);
+}
+
+/// A long overlap is one entry, however long it is.
+#[test]
+fn board_simple_long_overlap() {
+  let board = "R2000000000\nR2000000000,U1\nU1,R5,D2".parse::<Board>().unwrap();
+  assert_eq!(board.meeting_points(2), vec![
+    (
+      Intersection::Overlap(Overlap::new(Point::new(1, 0), Point::new(4, 0))),
+      vec![0, 1]
+    ),
+    (Intersection::Point(Point::new(5, 0)), vec![0, 1, 2]),
+    (
+      Intersection::Overlap(Overlap::new(
+        Point::new(6, 0),
+        Point::new(2_000_000_000, 0)
+      )),
+      vec![0, 1]
+    ),
+  ]);
+  assert_eq!(board.meeting_points(3), vec![(
+    Intersection::Point(Point::new(5, 0)),
+    vec![0, 1, 2]
+  )]);
+}
+
+#[test]
+fn intersection_simple_points() {
+  assert_eq!(Intersection::Point(Point::new(2, 3)).points(), vec![Point::new(
+    2, 3
+  )]);
+  let overlap =
+    Intersection::Overlap(Overlap::new(Point::new(1, -1), Point::new(1, 1)));
+  assert_eq!(overlap.points(), vec![
+    Point::new(1, -1),
+    Point::new(1, 0),
+    Point::new(1, 1)
+  ]);
+}
+
+#[test]
+fn board_simple_closest_crossing_matrix() {
+  let board = format!("{THREE_WIRES}\nD5").parse::<Board>().unwrap();
+  let matrix = board.closest_crossing_matrix(Frame::DEFAULT);
+  assert_eq!(matrix[0][1], Some(Point::new(3, -3)));
+  assert_eq!(matrix[1][0], Some(Point::new(3, -3)));
+  assert_eq!(matrix[1][2], Some(Point::new(0, -1)));
+  assert_eq!(matrix[0][3], None);
+  assert_eq!(matrix[3][3], None);
+  assert_eq!(
+    board.closest_crossing_matrix(Frame::new(Point::new(7, -6)))[0][2],
+    Some(Point::new(6, -5))
+  );
+}
+
+/// The points where at least `k` of the wires meet, and which wires they are,
+/// found by walking every wire one step at a time.
+fn brute_force(paths: &[Path], k: usize) -> Vec<(Point, Vec<usize>)> {
+  let mut expected: HashMap<Point, Vec<usize>> = HashMap::new();
+  for (i, path) in paths.iter().enumerate() {
+    for p in walk(Point::ORIGIN, path) {
+      let wires = expected.entry(p).or_default();
+      if wires.last() != Some(&i) {
+        wires.push(i);
+      }
+    }
+  }
+  let mut expected: Vec<(Point, Vec<usize>)> = expected
+    .into_iter()
+    .filter(|(p, wires)| *p != Point::ORIGIN && wires.len() >= k)
+    .collect();
+  expected.sort();
+  expected
+}
+
+/// `Board::meeting_points` with every overlap listed point by point.
+fn meeting_points(paths: &[Path], k: usize) -> Vec<(Point, Vec<usize>)> {
+  let wires = paths.iter().map(|p| Wire::try_from(p).unwrap()).collect();
+  let mut points: Vec<(Point, Vec<usize>)> = Board::new(wires)
+    .meeting_points(k)
+    .into_iter()
+    .flat_map(|(intersection, wires)| {
+      intersection.points().into_iter().map(move |p| (p, wires.clone()))
+    })
+    .collect();
+  points.sort();
+  points
+}
+
+proptest! {
+  #[test]
+  fn board_pb_matches_brute_force(
+    paths in prop::collection::vec(arb_path(8, 10), 1..5),
+    k in 2_usize..4,
+  ) {
+    prop_assert_eq!(meeting_points(&paths, k), brute_force(&paths, k));
+  }
+
+  #[test]
+  fn board_pb_diagonal_matches_brute_force(
+    paths in prop::collection::vec(arb_diagonal_path(8, 10), 1..5),
+    k in 2_usize..4,
+  ) {
+    prop_assert_eq!(meeting_points(&paths, k), brute_force(&paths, k));
+  }
+
+  #[test]
+  fn board_pb_two_wires_match_solver(p1 in arb_path(8, 10),
+                                     p2 in arb_path(8, 10)) {
+    let wire1 = Wire::try_from(&p1).unwrap();
+    let wire2 = Wire::try_from(&p2).unwrap();
+    let board = Board::new(vec![wire1, wire2]);
+    let closest = board.closest_crossing_matrix(Frame::DEFAULT)[0][1];
+    let distance = closest.map(|p| p.manhattan_distance());
+    prop_assert_eq!(distance, problem_3a(&format!("{p1}\n{p2}")).ok());
+  }
+}
@@ -0,0 +1,134 @@
+use aoc2019_3::board::Board;
+use aoc2019_3::diagram::*;
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+mod common;
+use common::*;
+
+fn board(input: &str) -> Board { input.parse().unwrap() }
+
+/// The first example of the puzzle statement, drawn as it is there.
+const EXAMPLE: &str = "\
+...........
+.+-----+...
+.|.....|...
+.|..+--X-+.
+.|..|..|.|.
+.|.-X--+.|.
+.|..|....|.
+.|.......|.
+.o-------+.
+...........
+";
+
+#[test]
+fn diagram_simple_example() {
+  assert_eq!(render(&board("R8,U5,L5,D3\nU7,R6,D4,L4")), EXAMPLE);
+}
+
+#[test]
+fn diagram_simple_single_wire() {
+  assert_eq!(
+    render(&board("R8,U5,L5,D3")),
+    "\
+...........
+....+----+.
+....|....|.
+....|....|.
+....|....|.
+.........|.
+.o-------+.
+...........
+"
+  );
+}
+
+#[test]
+fn diagram_simple_wire_crosses_itself() {
+  assert_eq!(
+    render(&board("R3,D2,L1,U4")),
+    "\
+......
+...|..
+...|..
+.o-++.
+...||.
+...++.
+......
+"
+  );
+}
+
+#[test]
+fn diagram_simple_diagonals() {
+  assert_eq!(
+    render(&board("UR3,D3\nDR2,U4")),
+    "\
+......
+....+.
+...X|.
+../||.
+.o.||.
+..\\|..
+...+..
+......
+"
+  );
+}
+
+#[test]
+fn diagram_simple_empty() {
+  assert_eq!(render(&board("")), "...\n.o.\n...\n");
+}
+
+#[test]
+fn diagram_simple_overlap() {
+  assert_eq!(render(&board("R4\nL1,R3")), "........\n.-oXX--.\n........\n");
+}
+
+#[test]
+fn diagram_simple_crop() {
+  let board = board("R8,U5,L5,D3\nU7,R6,D4,L4");
+  assert_eq!(render_around(&board, Point::new(3, -3), 1), ".|.\n-X-\n.|.\n");
+  assert_eq!(render_around(&board, Point::new(6, -5), 0), "X\n");
+  assert_eq!(render_around(&board, Point::new(100, 100), 0), ".\n");
+  assert_eq!(
+    render_around(&board, Point::new(4, -4), 5),
+    format!("...........\n{EXAMPLE}")
+  );
+}
+
+#[test]
+fn diagram_simple_crop_at_the_edge() {
+  let board = board("R8,U5,L5,D3\nU7,R6,D4,L4");
+  assert_eq!(render_around(&board, Point::new(i32::MAX, 0), 1), "..\n..\n..\n");
+  assert_eq!(render_around(&board, Point::new(i32::MIN, i32::MIN), 0), ".\n");
+  assert_eq!(
+    render_around(&board, Point::new(i32::MIN + 1, 0), 2),
+    "....\n....\n....\n....\n....\n"
+  );
+}
+
+proptest! {
+  #[test]
+  fn diagram_pb_cells_visited(p in arb_path(8, 10)) {
+    let wire = Wire::try_from(&p).unwrap();
+    let mut visited = HashSet::new();
+    let mut last = Point::ORIGIN;
+    visited.insert(last);
+    for entry in &p.moves {
+      let step = PathEntry::new(entry.get_direction(), 1);
+      for _ in 0..entry.get_distance() {
+        last = last.walk(&step);
+        visited.insert(last);
+      }
+    }
+    let diagram = render(&Board::new(vec![wire]));
+    prop_assert_eq!(diagram.chars().filter(|c| "-|+o".contains(*c)).count(),
+                    visited.len());
+    prop_assert!(!diagram.contains('X'));
+  }
+}
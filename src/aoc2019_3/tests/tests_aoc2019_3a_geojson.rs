@@ -0,0 +1,148 @@
+use aoc2019_3::board::Board;
+use aoc2019_3::geojson::*;
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+use serde_json::{Value, json};
+use std::convert::TryFrom;
+
+mod common;
+use common::*;
+
+fn board(input: &str) -> Board { input.parse().unwrap() }
+
+/// The GeoJSON position of `point`, with `y` negated.
+fn position(point: Point) -> Value {
+  json!([point.get_x(), -i64::from(point.get_y())])
+}
+
+#[test]
+fn geojson_simple_line_string() {
+  let wire = Wire::try_from(&Path::new("R8,U5,L5,D3")).unwrap();
+  assert_eq!(
+    line_string(&wire).unwrap(),
+    "{\"type\":\"LineString\",\"coordinates\":[[0,0],[8,0],[8,5],[3,5],[3,2]]}"
+  );
+  assert_eq!(
+    line_string(&Wire::try_from(&Path::new("")).unwrap()).unwrap(),
+    "{\"type\":\"LineString\",\"coordinates\":[[0,0],[0,0]]}"
+  );
+  assert_eq!(line_string(&Wire::new(vec![])), None);
+}
+
+#[test]
+fn geojson_simple_multi_point() {
+  assert_eq!(
+    multi_point(&[Point::new(3, -3), Point::new(6, -5)]),
+    "{\"type\":\"MultiPoint\",\"coordinates\":[[3,3],[6,5]]}"
+  );
+  assert_eq!(multi_point(&[]), "{\"type\":\"MultiPoint\",\"coordinates\":[]}");
+}
+
+#[test]
+fn geojson_simple_multi_line_string() {
+  assert_eq!(
+    multi_line_string(&[Overlap::new(Point::new(5, 0), Point::new(2, 0))]),
+    "{\"type\":\"MultiLineString\",\"coordinates\":[[[2,0],[5,0]]]}"
+  );
+  assert_eq!(
+    multi_line_string(&[]),
+    "{\"type\":\"MultiLineString\",\"coordinates\":[]}"
+  );
+}
+
+#[test]
+fn geojson_simple_example() {
+  let geojson = render(&board("R8,U5,L5,D3\nU7,R6,D4,L4"));
+  let value: Value = serde_json::from_str(&geojson).unwrap();
+  assert_eq!(value["type"], "FeatureCollection");
+  let features = value["features"].as_array().unwrap();
+  assert_eq!(features.len(), 4);
+  assert_eq!(features[0]["type"], "Feature");
+  assert_eq!(features[0]["properties"], json!({"wire": 0}));
+  assert_eq!(
+    features[1]["geometry"]["coordinates"],
+    json!([[0, 0], [0, 7], [6, 7], [6, 3], [2, 3]])
+  );
+  assert_eq!(features[1]["properties"], json!({"wire": 1}));
+  assert_eq!(features[2]["geometry"]["type"], "MultiPoint");
+  assert_eq!(features[2]["geometry"]["coordinates"], json!([[3, 3], [6, 5]]));
+  assert_eq!(features[2]["properties"], json!({"name": "crossings"}));
+  assert_eq!(features[3]["geometry"]["type"], "MultiLineString");
+  assert_eq!(features[3]["geometry"]["coordinates"], json!([]));
+  assert_eq!(features[3]["properties"], json!({"name": "overlaps"}));
+}
+
+/// An overlap is one line, however long, and its points are not crossings.
+#[test]
+fn geojson_simple_overlap() {
+  let geojson = render(&board("R2000000000\nU1,R5,D2,R2,U1,R2000000000"));
+  let value: Value = serde_json::from_str(&geojson).unwrap();
+  let features = value["features"].as_array().unwrap();
+  assert_eq!(features[2]["geometry"]["coordinates"], json!([[5, 0]]));
+  assert_eq!(
+    features[3]["geometry"]["coordinates"],
+    json!([[[7, 0], [2_000_000_000, 0]]])
+  );
+}
+
+#[test]
+fn geojson_simple_no_wires() {
+  let value: Value = serde_json::from_str(&render(&board(""))).unwrap();
+  assert_eq!(
+    value["features"],
+    json!([{
+      "type": "Feature",
+      "geometry": {"type": "MultiPoint", "coordinates": []},
+      "properties": {"name": "crossings"},
+    }, {
+      "type": "Feature",
+      "geometry": {"type": "MultiLineString", "coordinates": []},
+      "properties": {"name": "overlaps"},
+    }])
+  );
+}
+
+/// A wire without corners, as `Wire::new(vec![])` makes, has no valid
+/// `LineString`, so it is left out.
+#[test]
+fn geojson_simple_wire_without_corners() {
+  let wire = Wire::try_from(&Path::new("R2")).unwrap();
+  let board = Board::new(vec![Wire::new(vec![]), wire]);
+  let value: Value = serde_json::from_str(&render(&board)).unwrap();
+  let features = value["features"].as_array().unwrap();
+  assert_eq!(features.len(), 3);
+  assert_eq!(features[0]["geometry"]["coordinates"], json!([[0, 0], [2, 0]]));
+  assert_eq!(features[0]["properties"], json!({"wire": 1}));
+  assert_eq!(features[1]["properties"], json!({"name": "crossings"}));
+}
+
+proptest! {
+  #[test]
+  fn geojson_pb_render(p1 in arb_diagonal_path(10, 10_000),
+                       p2 in arb_diagonal_path(10, 10_000)) {
+    let wire1 = Wire::try_from(&p1).unwrap();
+    let wire2 = Wire::try_from(&p2).unwrap();
+    let board = Board::new(vec![wire1, wire2]);
+    let value: Value = serde_json::from_str(&render(&board)).unwrap();
+    let features = value["features"].as_array().unwrap();
+    prop_assert_eq!(features.len(), 4);
+    for (feature, wire) in features.iter().zip(board.get_wires()) {
+      let corners: Vec<Value> =
+        wire.get_corners().iter().map(|&p| position(p)).collect();
+      prop_assert_eq!(&feature["geometry"]["type"], "LineString");
+      prop_assert_eq!(&feature["geometry"]["coordinates"], &json!(corners));
+    }
+    let (mut crossings, mut overlaps) = (vec![], vec![]);
+    for (intersection, _) in board.meeting_points(2) {
+      match intersection {
+        Intersection::Point(p) => crossings.push(position(p)),
+        Intersection::Overlap(o) => {
+          let (start, end) = (position(o.get_start()), position(o.get_end()));
+          overlaps.push(json!([start, end]));
+        },
+      }
+    }
+    prop_assert_eq!(&features[2]["geometry"]["coordinates"], &json!(crossings));
+    prop_assert_eq!(&features[3]["geometry"]["coordinates"], &json!(overlaps));
+  }
+}
@@ -0,0 +1,77 @@
+use aoc2019_3::index::SectionIndex;
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+use std::convert::TryFrom;
+
+mod common;
+use common::*;
+
+/// The sections of `wire` through `point`, found by checking every one.
+fn brute_force(wire: &Wire, point: Point) -> Vec<usize> {
+  wire
+    .get_sections()
+    .iter()
+    .enumerate()
+    .filter(|(_, section)| section.contains(&point))
+    .map(|(i, _)| i)
+    .collect()
+}
+
+#[test]
+fn index_simple_problem1() {
+  let wire = Wire::try_from(&Path::new("R8,U5,L5,D3")).unwrap();
+  let index = SectionIndex::new(&wire);
+  assert_eq!(index.sections_at(Point::new(3, 0)), vec![0]);
+  assert_eq!(index.sections_at(Point::new(8, 0)), vec![0, 1]);
+  assert_eq!(index.sections_at(Point::new(3, -5)), vec![2, 3]);
+  assert_eq!(index.sections_at(Point::new(3, -3)), vec![3]);
+  assert_eq!(index.sections_at(Point::new(3, -1)), vec![]);
+  assert_eq!(index.steps_to(&Point::new(3, -3)), Some(20));
+  assert_eq!(index.steps_to(&Point::new(1, 1)), None);
+  assert_eq!(index.get_wire(), &wire);
+}
+
+/// A long section that starts early along a line still covers points past
+/// shorter sections that start after it.
+#[test]
+fn index_simple_nested_spans() {
+  let wire = Wire::try_from(&Path::new("R20,U1,L15,D1,R2,U2,R2")).unwrap();
+  let index = SectionIndex::new(&wire);
+  assert_eq!(index.sections_at(Point::new(6, 0)), vec![0, 4]);
+  assert_eq!(index.sections_at(Point::new(10, 0)), vec![0]);
+  assert_eq!(index.steps_to(&Point::new(6, 0)), Some(6));
+  assert_eq!(index.steps_to(&Point::new(7, -1)), Some(34));
+}
+
+#[test]
+fn index_simple_diagonals() {
+  let wire = Wire::try_from(&Path::new("UR3,D3,L1,UL2")).unwrap();
+  let index = SectionIndex::new(&wire);
+  assert_eq!(index.sections_at(Point::new(2, -2)), vec![0]);
+  assert_eq!(index.sections_at(Point::new(0, -2)), vec![3]);
+  assert_eq!(index.sections_at(Point::new(1, -1)), vec![0, 3]);
+  assert_eq!(index.steps_to(&Point::new(1, -1)), Some(1));
+}
+
+proptest! {
+  #[test]
+  fn index_pb_sections_at(p in arb_diagonal_path(20, 10),
+                          x in -30..30,
+                          y in -30..30) {
+    let wire = Wire::try_from(&p).unwrap();
+    let index = SectionIndex::new(&wire);
+    let point = Point::new(x, y);
+    prop_assert_eq!(index.sections_at(point), brute_force(&wire, point));
+    prop_assert_eq!(index.steps_to(&point), wire.steps_to(&point));
+  }
+
+  #[test]
+  fn index_pb_corners(p in arb_diagonal_path(20, 10)) {
+    let wire = Wire::try_from(&p).unwrap();
+    let index = SectionIndex::new(&wire);
+    for corner in wire.get_corners() {
+      prop_assert_eq!(index.sections_at(*corner), brute_force(&wire, *corner));
+      prop_assert_eq!(index.steps_to(corner), wire.steps_to(corner));
+    }
+  }
+}
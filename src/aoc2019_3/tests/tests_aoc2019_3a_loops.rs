@@ -0,0 +1,189 @@
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+
+mod common;
+use common::*;
+
+/// Every point `p` arrives at more than once, not counting the return to the
+/// central port at the end of a closed wire, unless its last step goes back
+/// over its first.
+fn revisited(p: &Path) -> BTreeSet<Point> {
+  let mut points = walk(Point::ORIGIN, p);
+  if points.len() > 2
+    && points.first() == points.last()
+    && points[1] != points[points.len() - 2]
+  {
+    points.pop();
+  }
+  let mut visits = BTreeMap::new();
+  for point in points {
+    *visits.entry(point).or_insert(0) += 1;
+  }
+  visits.into_iter().filter(|&(_, n)| n > 1).map(|(p, _)| p).collect()
+}
+
+fn wire(path: &str) -> Wire { Wire::try_from(&Path::new(path)).unwrap() }
+
+#[test]
+fn loops_simple_straight_back() {
+  for path in &["R1,L1", "UL1,DR1"] {
+    let wire = wire(path);
+    assert!(wire.is_closed());
+    assert_eq!(wire.self_crossings().len(), 1);
+    assert!(revisited(&Path::new(path)).contains(&Point::ORIGIN));
+  }
+}
+
+#[test]
+fn loops_simple_open() {
+  let wire = wire("R8,U5,L5,D3");
+  assert!(!wire.is_closed());
+  assert_eq!(wire.self_crossings(), vec![]);
+  assert_eq!(wire.first_revisit(), None);
+  assert_eq!(wire.twice_enclosed_area(), None);
+}
+
+#[test]
+fn loops_simple_empty() {
+  let wire = wire("");
+  assert!(!wire.is_closed());
+  assert_eq!(wire.self_crossings(), vec![]);
+  assert_eq!(wire.first_revisit(), None);
+  assert_eq!(wire.twice_enclosed_area(), None);
+}
+
+#[test]
+fn loops_simple_crossing() {
+  let wire = wire("R8,U5,L5,D8");
+  assert!(!wire.is_closed());
+  assert_eq!(wire.self_crossings(), vec![Intersection::Point(Point::new(
+    3, 0
+  ))]);
+  assert_eq!(wire.first_revisit(), Some((Point::new(3, 0), 23)));
+}
+
+#[test]
+fn loops_simple_doubling_back() {
+  let wire1 = wire("R5,L2");
+  assert_eq!(wire1.self_crossings(), vec![Intersection::Overlap(
+    Overlap::new(Point::new(3, 0), Point::new(5, 0))
+  )]);
+  assert_eq!(wire1.first_revisit(), Some((Point::new(4, 0), 6)));
+
+  let wire2 = wire("R3,U1,D1");
+  // Coming back down also touches the first section, where it turned off.
+  assert_eq!(wire2.self_crossings(), vec![
+    Intersection::Point(Point::new(3, 0)),
+    Intersection::Overlap(Overlap::new(Point::new(3, -1), Point::new(3, 0))),
+  ]);
+  assert_eq!(wire2.first_revisit(), Some((Point::new(3, 0), 5)));
+}
+
+#[test]
+fn loops_simple_square() {
+  let wire = wire("R2,U2,L2,D2");
+  assert!(wire.is_closed());
+  assert_eq!(wire.self_crossings(), vec![]);
+  assert_eq!(wire.first_revisit(), Some((Point::ORIGIN, 8)));
+  assert_eq!(wire.twice_enclosed_area(), Some(8));
+}
+
+#[test]
+fn loops_simple_triangle() {
+  let wire = wire("R1,U1,DL1");
+  assert!(wire.is_closed());
+  assert_eq!(wire.self_crossings(), vec![]);
+  assert_eq!(wire.first_revisit(), Some((Point::ORIGIN, 3)));
+  assert_eq!(wire.twice_enclosed_area(), Some(1));
+}
+
+/// A triangle as large as the grid allows, whose area an `f64` sum would
+/// round.
+#[test]
+fn loops_simple_large_triangle() {
+  let side = i32::MAX;
+  for path in
+    &[format!("R{side},D{side},UL{side}"), format!("L{side},U{side},DR{side}")]
+  {
+    assert_eq!(
+      wire(path).twice_enclosed_area(),
+      Some(4_611_686_014_132_420_609)
+    );
+  }
+}
+
+/// The two halves of a figure of eight are wound in opposite directions, so
+/// their areas cancel.
+#[test]
+fn loops_simple_figure_of_eight() {
+  let wire = wire("R2,U2,L1,D4,L1,U2");
+  assert!(wire.is_closed());
+  assert_eq!(wire.self_crossings(), vec![Intersection::Point(Point::new(
+    1, 0
+  ))]);
+  assert_eq!(wire.first_revisit(), Some((Point::new(1, 0), 7)));
+  assert_eq!(wire.twice_enclosed_area(), Some(0));
+}
+
+#[test]
+fn loops_simple_there_and_back() {
+  let wire = wire("R5,L5");
+  assert!(wire.is_closed());
+  assert_eq!(wire.self_crossings(), vec![Intersection::Overlap(Overlap::new(
+    Point::ORIGIN,
+    Point::new(5, 0)
+  ))]);
+  assert_eq!(wire.first_revisit(), Some((Point::new(4, 0), 6)));
+  assert_eq!(wire.twice_enclosed_area(), Some(0));
+}
+
+proptest! {
+  #[test]
+  fn loops_pb_first_revisit(p in arb_diagonal_path(10, 10)) {
+    let points = walk(Point::ORIGIN, &p);
+    let mut seen = BTreeSet::new();
+    let expected = points
+      .iter()
+      .zip(0..)
+      .find(|&(point, _)| !seen.insert(*point))
+      .map(|(point, steps)| (*point, steps));
+    prop_assert_eq!(Wire::try_from(&p).unwrap().first_revisit(), expected);
+  }
+
+  #[test]
+  fn loops_pb_self_crossings(p in arb_diagonal_path(10, 10)) {
+    let wire = Wire::try_from(&p).unwrap();
+    let crossings = wire.self_crossings();
+    let points: BTreeSet<Point> =
+      crossings.iter().flat_map(Intersection::points).collect();
+    let revisited = revisited(&p);
+    prop_assert_eq!(crossings.is_empty(), revisited.is_empty());
+    prop_assert!(revisited.is_subset(&points));
+    for point in &points {
+      prop_assert!(revisited.contains(point)
+                   || wire.get_corners().contains(point));
+    }
+  }
+
+  #[test]
+  fn loops_pb_rectangle_area(a in 1..10_000, b in 1..10_000) {
+    let p = Path::new(&format!("R{a},U{b},L{a},D{b}"));
+    let wire = Wire::try_from(&p).unwrap();
+    prop_assert!(wire.self_crossings().is_empty());
+    prop_assert_eq!(wire.twice_enclosed_area(), Some(2 * (a * b) as u128));
+    prop_assert_eq!(Wire::try_from(&p.reverse()).unwrap().twice_enclosed_area(),
+                    wire.twice_enclosed_area());
+  }
+
+  #[test]
+  fn loops_pb_there_and_back(p in arb_diagonal_path(10, 10)) {
+    let wire = Wire::try_from(&p.concat(&p.reverse())).unwrap();
+    prop_assert!(wire.is_closed());
+    prop_assert_eq!(wire.twice_enclosed_area(), Some(0));
+    prop_assert!(!wire.self_crossings().is_empty());
+    prop_assert_eq!(Wire::try_from(&p).unwrap().twice_enclosed_area().is_some(),
+                    Wire::try_from(&p).unwrap().is_closed());
+  }
+}
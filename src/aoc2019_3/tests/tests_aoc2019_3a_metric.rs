@@ -0,0 +1,190 @@
+use aoc2019_3::metric::{self, Chebyshev, Manhattan, Metric, SquaredEuclidean};
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+use std::convert::TryFrom;
+
+mod common;
+use common::*;
+
+prop_compose! {
+  /// Generate `Point` values anywhere on the grid
+  fn arb_point()(x in any::<i32>(), y in any::<i32>()) -> Point {
+    Point::new(x, y)
+  }
+}
+
+/// Generate each of the metrics
+fn strategy_metric() -> impl Strategy<Value = &'static dyn Metric> {
+  prop_oneof![
+    Just(&Manhattan as &dyn Metric),
+    Just(&Chebyshev as &dyn Metric),
+    Just(&SquaredEuclidean as &dyn Metric),
+  ]
+}
+
+#[test]
+fn metric_simple_distances() {
+  let (from, to) = (Point::new(1, -2), Point::new(4, 2));
+  assert_eq!(Manhattan.distance(from, to), 7);
+  assert_eq!(Chebyshev.distance(from, to), 4);
+  assert_eq!(SquaredEuclidean.distance(from, to), 25);
+}
+
+/// Opposite corners of the grid are too far apart for an `i64` once squared.
+#[test]
+fn metric_simple_extremes() {
+  let (from, to) =
+    (Point::new(i32::MIN, i32::MIN), Point::new(i32::MAX, i32::MAX));
+  assert_eq!(Manhattan.distance(from, to), 2 * ((1 << 32) - 1));
+  assert_eq!(Chebyshev.distance(from, to), (1 << 32) - 1);
+  assert_eq!(
+    SquaredEuclidean.distance(from, to),
+    2 * ((1 << 32) - 1) * ((1 << 32) - 1)
+  );
+}
+
+#[test]
+fn metric_simple_closer() {
+  let (p1, p2) = (Point::new(3, 3), Point::new(0, 5));
+  assert_eq!(Manhattan.closer(Point::ORIGIN, &p1, &p2), &p2);
+  assert_eq!(Chebyshev.closer(Point::ORIGIN, &p1, &p2), &p1);
+  assert_eq!(SquaredEuclidean.closer(Point::ORIGIN, &p1, &p2), &p1);
+  let p3 = Point::new(-6, 0);
+  assert_eq!(
+    Manhattan.closer(Point::ORIGIN, &p1, &p3),
+    &p1,
+    "Ties prefer point1"
+  );
+}
+
+#[test]
+fn metric_simple_named() {
+  assert_eq!(
+    metric::named("chebyshev")
+      .unwrap()
+      .distance(Point::ORIGIN, Point::new(2, 5)),
+    5
+  );
+  assert_eq!(
+    metric::named("euclidean").err(),
+    Some(
+      "Not one of manhattan, chebyshev, squared-euclidean: euclidean".into()
+    )
+  );
+}
+
+/// The closest point of a diagonal overlap is different for each metric.
+#[test]
+fn metric_simple_closest_by() {
+  let overlap = Overlap::new(Point::new(-4, 2), Point::new(2, -4));
+  assert_eq!(overlap.closest_by(Point::ORIGIN, &Manhattan), Point::new(-2, 0));
+  assert_eq!(overlap.closest_by(Point::ORIGIN, &Chebyshev), Point::new(-1, -1));
+  assert_eq!(
+    overlap.closest_by(Point::ORIGIN, &SquaredEuclidean),
+    Point::new(-1, -1)
+  );
+}
+
+/// The first example has crossings at `(3, -3)` and `(6, -5)`.
+#[test]
+fn metric_simple_solve_by() {
+  let input = "R8,U5,L5,D3\nU7,R6,D4,L4";
+  let (wire1, wire2) = {
+    let (p1, p2) = parse_input(input).unwrap();
+    (Wire::try_from(&p1).unwrap(), Wire::try_from(&p2).unwrap())
+  };
+  assert_eq!(solve_by(input, SolverMode::ClosestDistance, &Manhattan), Ok(6));
+  assert_eq!(solve_by(input, SolverMode::ClosestDistance, &Chebyshev), Ok(3));
+  assert_eq!(
+    solve_by(input, SolverMode::ClosestDistance, &SquaredEuclidean),
+    Ok(18)
+  );
+  assert_eq!(solve_by(input, SolverMode::FewestSteps, &Chebyshev), Ok(30));
+  // From here, one crossing is closer by Manhattan distance and the other in
+  // a straight line.
+  let frame = Frame::new(Point::new(6, -1));
+  assert_eq!(
+    frame.closest_crossing_by(&wire1, &wire2, &Manhattan),
+    Some(Point::new(6, -5))
+  );
+  assert_eq!(
+    frame.closest_crossing_by(&wire1, &wire2, &SquaredEuclidean),
+    Some(Point::new(3, -3))
+  );
+  assert_eq!(
+    solve_by("R5\nL5", SolverMode::ClosestDistance, &Chebyshev),
+    Err("The wires do not cross".into())
+  );
+}
+
+proptest! {
+  #[test]
+  fn metric_pb_axioms(metric in strategy_metric(),
+                      p1 in arb_point(),
+                      p2 in arb_point()) {
+    prop_assert_eq!(metric.distance(p1, p1), 0);
+    prop_assert_eq!(metric.distance(p1, p2), metric.distance(p2, p1));
+    prop_assert!(metric.distance(p1, p2) >= 0);
+    prop_assert_eq!(metric.distance(p1, p2) == 0, p1 == p2);
+  }
+
+  #[test]
+  fn metric_pb_bounds(p1 in arb_point(), p2 in arb_point()) {
+    let manhattan = Manhattan.distance(p1, p2);
+    let chebyshev = Chebyshev.distance(p1, p2);
+    prop_assert_eq!(manhattan, i128::from(p1.steps_to(&p2)));
+    prop_assert_eq!(chebyshev, i128::from(p1.steps_along(&p2)));
+    prop_assert!(chebyshev <= manhattan && manhattan <= 2 * chebyshev);
+    let squared = SquaredEuclidean.distance(p1, p2);
+    prop_assert!(chebyshev * chebyshev <= squared);
+    prop_assert!(squared <= manhattan * manhattan);
+  }
+
+  #[test]
+  fn metric_pb_closest_by(metric in strategy_metric(),
+                          x in -20..20, y in -20..20, length in 1..20,
+                          slope in -1..=1, px in -30..30, py in -30..30) {
+    let end = Point::new(x + length, y + slope * length);
+    let overlap = Overlap::new(Point::new(x, y), end);
+    let point = Point::new(px, py);
+    // The first of the closest points, which is the one nearest the start.
+    let best = overlap
+      .points()
+      .into_iter()
+      .min_by_key(|p| metric.distance(point, *p));
+    prop_assert_eq!(Some(overlap.closest_by(point, metric)), best);
+  }
+
+  #[test]
+  fn metric_pb_closest_crossing_by(metric in strategy_metric(),
+                                   p1 in arb_diagonal_path(8, 10),
+                                   p2 in arb_diagonal_path(8, 10)) {
+    let wire1 = Wire::try_from(&p1).unwrap();
+    let wire2 = Wire::try_from(&p2).unwrap();
+    let best = wire1
+      .crossings(&wire2)
+      .iter()
+      .flat_map(Intersection::points)
+      .filter(|p| *p != Point::ORIGIN)
+      .map(|p| metric.distance(Point::ORIGIN, p))
+      .min();
+    let closest = Frame::DEFAULT.closest_crossing_by(&wire1, &wire2, metric);
+    prop_assert_eq!(closest.map(|p| metric.distance(Point::ORIGIN, p)), best);
+    prop_assert_eq!(
+      solve_by(&format!("{p1}\n{p2}"), SolverMode::ClosestDistance, metric).ok(),
+      best
+    );
+  }
+
+  #[test]
+  fn metric_pb_manhattan_is_default(p1 in arb_diagonal_path(8, 10),
+                                    p2 in arb_diagonal_path(8, 10)) {
+    let input = format!("{p1}\n{p2}");
+    for mode in &[SolverMode::ClosestDistance, SolverMode::FewestSteps] {
+      prop_assert_eq!(
+        solve_by(&input, *mode, &Manhattan).ok(),
+        solve(&input, *mode).ok().map(i128::from)
+      );
+    }
+  }
+}
@@ -1,18 +1,9 @@
-#![feature(test)]
-
-use aoc2019_3::problem_3a::MovementDirection;
-use aoc2019_3::problem_3a::MovementDirection::{D, L, R, U};
+use aoc2019_3::problem_3a::MovementDirection::{D, DL, DR, L, R, U, UL, UR};
+use aoc2019_3::problem_3a::{MovementDirection, ParseError, ParseErrorKind};
 use proptest::prelude::*;
 
-/// Generate `MovementDirection` values
-fn strategy_movement_direction() -> impl Strategy<Value = MovementDirection> {
-  prop_oneof![
-    Just(MovementDirection::U),
-    Just(MovementDirection::D),
-    Just(MovementDirection::L),
-    Just(MovementDirection::R),
-  ]
-}
+mod common;
+use common::*;
 
 #[test]
 fn movement_direction_simple_up() {
@@ -43,19 +34,43 @@ fn movement_direction_simple_right() {
 }
 
 #[test]
-#[should_panic(expected = "Not one of U, D, L, R: X")]
-fn movement_direction_simple_x() { "X".parse::<MovementDirection>().unwrap(); }
+fn movement_direction_simple_diagonals() {
+  for (s, md) in &[("UL", UL), ("UR", UR), ("DL", DL), ("DR", DR)] {
+    assert_eq!(s.parse::<MovementDirection>().unwrap(), *md);
+    assert_eq!(s.to_lowercase().parse::<MovementDirection>().unwrap(), *md);
+    assert_eq!(format!("{}", md), *s);
+  }
+}
+
+/// The error for a direction that cannot be parsed from `s`
+fn bad_direction(s: &str) -> ParseError {
+  ParseError::new(ParseErrorKind::BadDirection(s.into()), 0, 0..s.len())
+}
+
+#[test]
+fn movement_direction_simple_reversed_diagonal() {
+  assert_eq!("LU".parse::<MovementDirection>(), Err(bad_direction("LU")));
+}
+
+#[test]
+fn movement_direction_simple_x() {
+  assert_eq!("X".parse::<MovementDirection>(), Err(bad_direction("X")));
+}
 
 #[test]
-#[should_panic(expected = "Value is not a single character: ")]
 fn movement_direction_simple_empty_string() {
-  "".parse::<MovementDirection>().unwrap();
+  assert_eq!("".parse::<MovementDirection>(), Err(bad_direction("")));
 }
 
 #[test]
-#[should_panic(expected = "Value is not a single character: uldr")]
 fn movement_direction_simple_long_string() {
-  "uldr".parse::<MovementDirection>().unwrap();
+  assert_eq!("uldr".parse::<MovementDirection>(), Err(bad_direction("uldr")));
+}
+
+#[test]
+#[should_panic(expected = "Not one of U, D, L, R, UL, UR, DL, DR: X")]
+fn movement_direction_simple_error_message() {
+  "X".parse::<MovementDirection>().map_err(|e| e.to_string()).unwrap();
 }
 
 proptest! {
@@ -65,6 +80,13 @@ proptest! {
    prop_assert!(md == U || md == D || md == L || md == R);
  }
 
+ #[test]
+ fn movement_direction_pb_eight_way_strategy_is_valid
+  (md in strategy_eight_way_direction()) {
+   prop_assert!([U, D, L, R, UL, UR, DL, DR].contains(&md));
+   prop_assert_eq!(md.to_string().parse::<MovementDirection>().unwrap(), md);
+ }
+
  #[test]
  fn movement_direction_pb_valid_input(s in "[uUdDlLrR]") {
    prop_assert!(s.parse::<MovementDirection>().is_ok());
@@ -76,7 +98,12 @@ proptest! {
  }
 
  #[test]
- fn movement_direction_pb_invalid_input_multichar(s in "..+") {
+ fn movement_direction_pb_valid_diagonal(s in "[uUdD][lLrR]") {
+   prop_assert!(s.parse::<MovementDirection>().is_ok());
+ }
+
+ #[test]
+ fn movement_direction_pb_invalid_input_multichar(s in "...+") {
    prop_assert!(s.parse::<MovementDirection>().is_err());
  }
 }
@@ -0,0 +1,154 @@
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+
+mod common;
+use common::*;
+
+/// Every unit step a wire takes, with its ends in order.
+fn edges(path: &Path) -> BTreeSet<(Point, Point)> {
+  let mut edges = BTreeSet::new();
+  let mut last = Point::ORIGIN;
+  for entry in &path.moves {
+    let step = PathEntry::new(entry.get_direction(), 1);
+    for _ in 0..entry.get_distance() {
+      let next = last.walk(&step);
+      edges.insert((last.min(next), last.max(next)));
+      last = next;
+    }
+  }
+  edges
+}
+
+fn wires(input: &str) -> (Wire, Wire) {
+  let (path1, path2) = parse_input(input).unwrap();
+  (Wire::try_from(&path1).unwrap(), Wire::try_from(&path2).unwrap())
+}
+
+#[test]
+fn overlap_simple_new_orders_points() {
+  let overlap = Overlap::new(Point::new(2, 7), Point::new(2, -1));
+  assert_eq!(overlap.get_start(), Point::new(2, -1));
+  assert_eq!(overlap.get_end(), Point::new(2, 7));
+  assert_eq!(overlap.length(), 8);
+  assert!(overlap.contains(&Point::new(2, 0)));
+  assert!(!overlap.contains(&Point::new(3, 0)));
+}
+
+#[test]
+#[should_panic(
+  expected = "A WireSection must be horizontal, vertical or diagonal"
+)]
+fn overlap_simple_not_straight() {
+  let _ = Overlap::new(Point::ORIGIN, Point::new(1, 2));
+}
+
+#[test]
+fn overlap_simple_diagonal() {
+  let overlap = Overlap::new(Point::new(3, -3), Point::new(1, -1));
+  assert_eq!(overlap.get_start(), Point::new(1, -1));
+  assert_eq!(overlap.length(), 2);
+  assert_eq!(overlap.points(), vec![
+    Point::new(1, -1),
+    Point::new(2, -2),
+    Point::new(3, -3),
+  ]);
+  assert!(overlap.contains(&Point::new(2, -2)));
+  assert!(!overlap.contains(&Point::new(2, -1)));
+}
+
+#[test]
+fn overlap_simple_closest_to() {
+  let horizontal = Overlap::new(Point::new(-5, 2), Point::new(5, 2));
+  assert_eq!(horizontal.closest_to(Point::new(1, 9)), Point::new(1, 2));
+  assert_eq!(horizontal.closest_to(Point::new(9, 0)), Point::new(5, 2));
+  // Along a diagonal the Manhattan distance is the same everywhere between
+  // the row and the column of the point, so the first of those is picked.
+  let diagonal = Overlap::new(Point::new(0, 0), Point::new(6, 6));
+  assert_eq!(diagonal.closest_to(Point::new(4, 1)), Point::new(1, 1));
+  assert_eq!(diagonal.closest_to(Point::new(-3, 9)), Point::new(0, 0));
+  let anti = Overlap::new(Point::new(0, 0), Point::new(6, -6));
+  assert_eq!(anti.closest_to(Point::new(4, -1)), Point::new(1, -1));
+}
+
+#[test]
+fn overlap_simple_points() {
+  assert_eq!(Overlap::new(Point::new(3, 1), Point::new(0, 1)).points(), vec![
+    Point::new(0, 1),
+    Point::new(1, 1),
+    Point::new(2, 1),
+    Point::new(3, 1),
+  ]);
+}
+
+#[test]
+fn overlap_simple_examples_do_not_overlap() {
+  let (wire1, wire2) = wires("R8,U5,L5,D3\nU7,R6,D4,L4");
+  assert_eq!(overlaps(&wire1, &wire2), vec![]);
+  assert_eq!(overlap_points(&wire1, &wire2), vec![]);
+}
+
+#[test]
+fn overlap_simple_segments() {
+  // The wires run together from the port to (0,-3), and again from (2,-5)
+  // round the corner at (4,-5) to (4,-3).
+  let (wire1, wire2) = wires("U5,R4,D2\nU3,R4,U2,L2");
+  assert_eq!(overlaps(&wire1, &wire2), vec![
+    Overlap::new(Point::new(0, -3), Point::ORIGIN),
+    Overlap::new(Point::new(2, -5), Point::new(4, -5)),
+    Overlap::new(Point::new(4, -5), Point::new(4, -3)),
+  ]);
+  assert_eq!(overlap_points(&wire1, &wire2), vec![
+    Point::new(0, -3),
+    Point::new(0, -2),
+    Point::new(0, -1),
+    Point::new(2, -5),
+    Point::new(3, -5),
+    Point::new(4, -5),
+    Point::new(4, -4),
+    Point::new(4, -3),
+  ]);
+}
+
+proptest! {
+  #[test]
+  fn overlap_pb_closest_to(x in -20..20, y in -20..20, length in 1..20,
+                           slope in -1..=1, px in -30..30, py in -30..30) {
+    let end = Point::new(x + length, y + slope * length);
+    let overlap = Overlap::new(Point::new(x, y), end);
+    let point = Point::new(px, py);
+    let best = overlap.points().iter().map(|p| p.steps_to(&point)).min();
+    let closest = overlap.closest_to(point);
+    prop_assert!(overlap.contains(&closest));
+    prop_assert_eq!(Some(closest.steps_to(&point)), best);
+  }
+
+  #[test]
+  fn overlap_pb_length(x in -50..50, y in -50..50, length in 1..50,
+                       vertical in any::<bool>()) {
+    let end = if vertical {
+      Point::new(x, y + length)
+    } else {
+      Point::new(x + length, y)
+    };
+    let overlap = Overlap::new(Point::new(x, y), end);
+    prop_assert_eq!(overlap.length(), i64::from(length));
+    prop_assert_eq!(overlap.points().len() as i64, overlap.length() + 1);
+  }
+
+  #[test]
+  fn overlap_pb_brute_force(p1 in arb_path(10, 6), p2 in arb_path(10, 6)) {
+    let shared: BTreeSet<Point> = edges(&p1)
+      .intersection(&edges(&p2))
+      .flat_map(|&(a, b)| [a, b])
+      .filter(|p| *p != Point::ORIGIN)
+      .collect();
+    let wire1 = Wire::try_from(&p1).unwrap();
+    let wire2 = Wire::try_from(&p2).unwrap();
+    prop_assert_eq!(
+      overlap_points(&wire1, &wire2),
+      shared.into_iter().collect::<Vec<_>>()
+    );
+  }
+}
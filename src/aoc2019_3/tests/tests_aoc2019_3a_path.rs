@@ -1,36 +1,15 @@
-#![feature(test)]
-
 use aoc2019_3::problem_3a::MovementDirection::{D, L, R, U};
 use aoc2019_3::problem_3a::*;
 use proptest::prelude::*;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
 
-/// Generate `MovementDirection` values
-fn strategy_movement_direction() -> impl Strategy<Value = MovementDirection> {
-  prop_oneof![
-    Just(MovementDirection::U),
-    Just(MovementDirection::D),
-    Just(MovementDirection::L),
-    Just(MovementDirection::R),
-  ]
-}
-
-prop_compose! {
-  /// Generate `PathEntry` values
-  fn arb_pathentry()
-                  (direction in strategy_movement_direction(),
-                   distance in 1..10_000)
-                  -> PathEntry {
-    PathEntry::new(direction, distance)
-  }
-}
+mod common;
+use common::*;
 
-prop_compose! {
-  /// Generate `Path` values
-  fn arb_path(max_segments: usize)
-             (vec in prop::collection::vec(arb_pathentry(), 1..max_segments))
-             -> Path {
-    Path::new_internal(vec)
-  }
+/// Where `p` ends when walked from the central port.
+fn end_point(p: &Path) -> Point {
+  *Wire::try_from(p).unwrap().get_corners().last().unwrap()
 }
 
 /// R8,U5,L5,D3
@@ -127,7 +106,189 @@ fn path_simple_empty_string() {
   assert_eq!(p_actual.moves.len(), 0);
 }
 
+#[test]
+fn path_simple_normalise() {
+  assert_eq!(Path::new("R3,R4").normalise(), Path::new("R7"));
+  assert_eq!(Path::new("R5,L2").normalise(), Path::new("R3"));
+  assert_eq!(Path::new("R2,L5").normalise(), Path::new("L3"));
+  assert_eq!(Path::new("R5,L5").normalise(), Path::new(""));
+  assert_eq!(Path::new("R3,U2,D2,R4").normalise(), Path::new("R7"));
+  assert_eq!(Path::new("R3,U2,D5,L1").normalise(), Path::new("R3,D3,L1"));
+  assert_eq!(Path::new("UR2,DL3,R1").normalise(), Path::new("DL1,R1"));
+  assert_eq!(Path::new("R8,U5,L5,D3").normalise(), Path::new("R8,U5,L5,D3"));
+}
+
+#[test]
+fn path_simple_normalise_overflow() {
+  let p =
+    Path::new_internal(vec![PathEntry::new(R, i32::MAX), PathEntry::new(R, 5)]);
+  assert_eq!(p.normalise(), p);
+  assert_eq!(
+    Path::new_internal(vec![
+      PathEntry::new(R, i32::MAX),
+      PathEntry::new(R, 5),
+      PathEntry::new(L, 10),
+    ])
+    .normalise(),
+    Path::new_internal(vec![PathEntry::new(R, i32::MAX - 5)])
+  );
+}
+
+#[test]
+fn path_simple_reverse() {
+  assert_eq!(Path::new("R8,U5,L5,D3").reverse(), Path::new("U3,R5,D5,L8"));
+  assert_eq!(Path::new("UR2,L1").reverse(), Path::new("R1,DL2"));
+  assert_eq!(Path::new("").reverse(), Path::new(""));
+}
+
+#[test]
+fn path_simple_concat() {
+  assert_eq!(
+    Path::new("R8,U5").concat(&Path::new("L5,D3")),
+    Path::new("R8,U5,L5,D3")
+  );
+  assert_eq!(Path::new("R8").concat(&Path::new("")), Path::new("R8"));
+}
+
+#[test]
+fn path_simple_same_points() {
+  assert!(Path::new("R8").same_points(&Path::new("R3,R5")));
+  assert!(Path::new("R8").same_points(&Path::new("R8,L8,R8")));
+  assert!(Path::new("R8").same_points(&Path::new("R3,L3,R8,L2")));
+  assert!(Path::new("R2,U2").same_points(&Path::new("R2,U2,D2,L2")));
+  assert!(Path::new("").same_points(&Path::new("")));
+  assert!(!Path::new("R8").same_points(&Path::new("R7")));
+  assert!(!Path::new("R8").same_points(&Path::new("R3,U1,D1,R5")));
+  assert!(!Path::new("R4,L8").same_points(&Path::new("R4")));
+  assert!(!Path::new("").same_points(&Path::new("R1")));
+  // The loops meet at the same points, but take different routes between
+  // them.
+  assert!(!Path::new("R2,U2,L2,D2").same_points(&Path::new("U2,R2,D2,L2,UR2")));
+  assert!(Path::new("R2,U2,L2,D2").same_points(&Path::new("U2,R2,D2,L2")));
+}
+
+#[test]
+fn path_simple_whitespace_and_trailing_comma() {
+  assert_eq!(Path::try_new(" R8 , U5,\tL5,D3 ,"), Ok(Path::new("R8,U5,L5,D3")));
+  assert_eq!(Path::try_new("  "), Ok(Path::new("")));
+}
+
+#[test]
+fn path_simple_all_errors() {
+  assert_eq!(
+    Path::try_new("R8,,X5, U0,L99999999999,D"),
+    Err(vec![
+      ParseError::new(ParseErrorKind::EmptyEntry, 1, 3..3),
+      ParseError::new(ParseErrorKind::BadDirection("X".into()), 2, 4..5),
+      ParseError::new(ParseErrorKind::ZeroDistance, 3, 9..10),
+      ParseError::new(
+        ParseErrorKind::Overflow("99999999999".into()),
+        4,
+        12..23
+      ),
+      ParseError::new(ParseErrorKind::BadDistance("".into()), 5, 25..25),
+    ])
+  );
+  assert_eq!(
+    ",R8".parse::<Path>(),
+    Err(ParseError::new(ParseErrorKind::EmptyEntry, 0, 0..0))
+  );
+  assert_eq!(
+    "R8,X5,U0".parse::<Path>(),
+    Err(ParseError::new(ParseErrorKind::BadDirection("X".into()), 1, 3..4))
+  );
+}
+
+#[test]
+fn path_simple_underline() {
+  let line = "R8, UX,L5";
+  let e = Path::try_new(line).unwrap_err().remove(0);
+  assert_eq!(e.underline(line), "R8, UX,L5\n     ^");
+  assert_eq!(
+    ParseError::new(ParseErrorKind::EmptyEntry, 1, 3..3).underline("R8,,U5"),
+    "R8,,U5\n   ^"
+  );
+}
+
+#[test]
+fn path_simple_parse_paths() {
+  assert_eq!(
+    parse_paths("R8,U5\n\nL5,D3\n"),
+    Ok(vec![Path::new("R8,U5"), Path::new("L5,D3")])
+  );
+  assert_eq!(
+    parse_paths("R8,U5\nL5,X3,D0"),
+    Err(
+      "Line 2: Not one of U, D, L, R, UL, UR, DL, DR: X in entry 1 at bytes \
+       3..4\nL5,X3,D0\n   ^\nLine 2: Zero distance not allowed in entry 2 at \
+       bytes 7..8\nL5,X3,D0\n       ^"
+        .into()
+    )
+  );
+}
+
+/// The points `p` passes through, each once.
+fn points(p: &Path) -> BTreeSet<Point> {
+  walk(Point::ORIGIN, p).into_iter().collect()
+}
+
 proptest! {
+  #[test]
+  fn path_pb_error_spans(s in "([ULDRuldrX]{0,2}[0-9-]{0,3},){1,8}") {
+    match Path::try_new(&s) {
+      Ok(p) => prop_assert_eq!(Ok(p), s.parse::<Path>().map_err(|_| ())),
+      Err(errors) => {
+        prop_assert_eq!(s.parse::<Path>(), Err(errors[0].clone()));
+        let fields: Vec<&str> = s.split(',').collect();
+        for pair in errors.windows(2) {
+          prop_assert!(pair[0].get_entry() < pair[1].get_entry());
+        }
+        for e in &errors {
+          // Every span lies within the entry it reports.
+          let start: usize =
+            fields[..e.get_entry()].iter().map(|f| f.len() + 1).sum();
+          let end = start + fields[e.get_entry()].len();
+          prop_assert!(start <= e.get_span().start);
+          prop_assert!(e.get_span().end <= end);
+        }
+      },
+    }
+  }
+
+  #[test]
+  fn path_pb_normalise_keeps_end_point(p in arb_path(50, 10_000)) {
+    let normalised = p.normalise();
+    prop_assert_eq!(end_point(&normalised), end_point(&p));
+    prop_assert_eq!(&normalised.normalise(), &normalised);
+    prop_assert!(normalised.moves.len() <= p.moves.len());
+    prop_assert!(points(&normalised).is_subset(&points(&p)));
+  }
+
+  #[test]
+  fn path_pb_reverse(p in arb_path(50, 10_000)) {
+    prop_assert_eq!(&p.reverse().reverse(), &p);
+    prop_assert_eq!(p.concat(&p.reverse()).normalise(), Path::new(""));
+  }
+
+  #[test]
+  fn path_pb_concat(p1 in arb_path(20, 10_000), p2 in arb_path(20, 10_000)) {
+    let p = p1.concat(&p2);
+    prop_assert_eq!(p.moves.len(), p1.moves.len() + p2.moves.len());
+    let (end1, end2) = (end_point(&p1), end_point(&p2));
+    prop_assert_eq!(
+      end_point(&p),
+      Point::new(end1.get_x() + end2.get_x(), end1.get_y() + end2.get_y())
+    );
+  }
+
+  #[test]
+  fn path_pb_same_points(p1 in arb_path(10, 10_000),
+                         p2 in arb_path(10, 10_000)) {
+    prop_assert_eq!(p1.same_points(&p2), points(&p1) == points(&p2));
+    prop_assert!(p1.same_points(&p1.concat(&p1.reverse())));
+    prop_assert!(p1.same_points(&p1.concat(&p1.reverse()).concat(&p1)));
+  }
+
   #[test]
   fn path_pb_valid_input(s in "([ULDRuldr][1-9][0-9]{0,4},)*") {
     prop_assert!(s.parse::<Path>().is_ok());
@@ -142,4 +303,9 @@ proptest! {
   fn path_pb_invalid_distance(s in "([ULDRuldr]-[0-9]{1,5},)+") {
     prop_assert!(s.parse::<Path>().is_err());
   }
+
+  #[test]
+  fn path_pb_display_round_trip(p in arb_path(50, 10_000)) {
+    prop_assert_eq!(p.to_string().parse::<Path>().unwrap(), p);
+  }
 }
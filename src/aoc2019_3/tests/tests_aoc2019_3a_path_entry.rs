@@ -1,28 +1,9 @@
-#![feature(test)]
-
-use aoc2019_3::problem_3a::MovementDirection::{D, L, R, U};
-use aoc2019_3::problem_3a::{MovementDirection, PathEntry};
+use aoc2019_3::problem_3a::MovementDirection::{D, DL, DR, L, R, U, UL, UR};
+use aoc2019_3::problem_3a::{ParseError, ParseErrorKind, PathEntry};
 use proptest::prelude::*;
 
-/// Generate `MovementDirection` values
-fn strategy_movement_direction() -> impl Strategy<Value = MovementDirection> {
-  prop_oneof![
-    Just(MovementDirection::U),
-    Just(MovementDirection::D),
-    Just(MovementDirection::L),
-    Just(MovementDirection::R),
-  ]
-}
-
-prop_compose! {
-  /// Generate `PathEntry` values
-  fn arb_pathentry()
-                  (direction in strategy_movement_direction(),
-                   distance in 1..10_000)
-                  -> PathEntry {
-    PathEntry::new(direction, distance)
-  }
-}
+mod common;
+use common::*;
 
 #[test]
 fn path_entry_simple_up() {
@@ -49,45 +30,96 @@ fn path_entry_simple_right() {
 }
 
 #[test]
-#[should_panic(expected = "Zero distance not allowed for PathEntry: U0")]
-fn path_entry_simple_0_distance() { "U0".parse::<PathEntry>().unwrap(); }
+fn path_entry_simple_diagonals() {
+  assert_eq!("UL42".parse::<PathEntry>().unwrap(), PathEntry::new(UL, 42));
+  assert_eq!("ur7".parse::<PathEntry>().unwrap(), PathEntry::new(UR, 7));
+  assert_eq!("DL1".parse::<PathEntry>().unwrap(), PathEntry::new(DL, 1));
+  assert_eq!("DR10".parse::<PathEntry>().unwrap(), PathEntry::new(DR, 10));
+  assert_eq!(
+    "ULX".parse::<PathEntry>(),
+    Err(ParseError::new(ParseErrorKind::BadDistance("X".into()), 0, 2..3))
+  );
+}
+
+#[test]
+fn path_entry_simple_0_distance() {
+  assert_eq!(
+    "U0".parse::<PathEntry>(),
+    Err(ParseError::new(ParseErrorKind::ZeroDistance, 0, 1..2))
+  );
+  assert_eq!(
+    "DL000".parse::<PathEntry>(),
+    Err(ParseError::new(ParseErrorKind::ZeroDistance, 0, 2..5))
+  );
+}
 
 #[test]
 fn path_entry_simple_empty_string() {
-  match "".parse::<PathEntry>() {
-    Err(msg) => assert_eq!(msg, "String not of type '[UDLR][0-9]+': "),
-    _ => panic!("Should never happen"),
-  }
+  assert_eq!(
+    "".parse::<PathEntry>(),
+    Err(ParseError::new(ParseErrorKind::EmptyEntry, 0, 0..0))
+  );
 }
 
 #[test]
 fn path_entry_simple_invalid_direction() {
-  match "X42".parse::<PathEntry>() {
-    Err(msg) => assert_eq!(msg, "Unable to parse direction from X42"),
-    _ => panic!("Should never happen"),
-  }
+  assert_eq!(
+    "X42".parse::<PathEntry>(),
+    Err(ParseError::new(ParseErrorKind::BadDirection("X".into()), 0, 0..1))
+  );
 }
 
 #[test]
 fn path_entry_simple_invalid_distance() {
-  match "UYZ".parse::<PathEntry>() {
-    Err(msg) => assert_eq!(msg, "Unable to parse distance from UYZ"),
-    _ => panic!("Should never happen"),
-  }
+  assert_eq!(
+    "UYZ".parse::<PathEntry>(),
+    Err(ParseError::new(ParseErrorKind::BadDistance("YZ".into()), 0, 1..3))
+  );
+  assert_eq!(
+    "R".parse::<PathEntry>(),
+    Err(ParseError::new(ParseErrorKind::BadDistance("".into()), 0, 1..1))
+  );
+  assert_eq!(
+    "R+4".parse::<PathEntry>(),
+    Err(ParseError::new(ParseErrorKind::BadDistance("+4".into()), 0, 1..3))
+  );
 }
 
 #[test]
 fn path_entry_simple_negative_distance() {
-  match "U-42".parse::<PathEntry>() {
-    Err(msg) =>
-      assert_eq!(msg, "Zero distance not allowed for PathEntry: U-42"),
-    _ => panic!("Should never happen"),
-  }
+  assert_eq!(
+    "U-42".parse::<PathEntry>(),
+    Err(ParseError::new(ParseErrorKind::BadDistance("-42".into()), 0, 1..4))
+  );
+}
+
+#[test]
+fn path_entry_simple_overflow() {
+  assert_eq!(
+    "R2147483647".parse::<PathEntry>(),
+    Ok(PathEntry::new(R, i32::MAX))
+  );
+  assert_eq!(
+    "UR2147483648".parse::<PathEntry>(),
+    Err(ParseError::new(
+      ParseErrorKind::Overflow("2147483648".into()),
+      0,
+      2..12
+    ))
+  );
+}
+
+#[test]
+fn path_entry_simple_error_message() {
+  assert_eq!(
+    "U0".parse::<PathEntry>().unwrap_err().to_string(),
+    "Zero distance not allowed in entry 0 at bytes 1..2"
+  );
 }
 
 proptest! {
  #[test]
- fn path_entry_pb_strategy_is_valid(pe in arb_pathentry()) {
+ fn path_entry_pb_strategy_is_valid(pe in arb_pathentry(10_000)) {
    let md = pe.get_direction();
    let dist = pe.get_distance();
    prop_assert!(md == U || md == D || md == L || md == R);
@@ -100,6 +132,12 @@ proptest! {
    prop_assert!(s.parse::<PathEntry>().is_ok());
  }
 
+ #[test]
+ fn path_entry_pb_valid_diagonal_input(md in "[uUdD][lLrR]", dist in 1..i32::MAX) {
+   let s = format!("{}{}", md, dist);
+   prop_assert!(s.parse::<PathEntry>().is_ok());
+ }
+
  #[test]
  fn path_entry_pb_invalid_direction(s in "[^uUdDlLrR][1-9][0-9]*") {
    prop_assert!(s.parse::<PathEntry>().is_err());
@@ -110,6 +148,23 @@ proptest! {
    prop_assert!(s.parse::<PathEntry>().is_err());
  }
 
+ #[test]
+ fn path_entry_pb_overflow(md in "[uUdD]?[lLrR]", dist in 2_147_483_648_u64..) {
+   let s = format!("{md}{dist}");
+   let kind = ParseErrorKind::Overflow(dist.to_string());
+   prop_assert_eq!(s.parse::<PathEntry>().map_err(|e| e.get_kind().clone()),
+                   Err(kind));
+ }
+
+ #[test]
+ fn path_entry_pb_error_span_in_entry(s in ".*") {
+   if let Err(e) = s.parse::<PathEntry>() {
+     prop_assert_eq!(e.get_entry(), 0);
+     prop_assert!(e.get_span().end <= s.len());
+     prop_assert!(s.get(e.get_span()).is_some());
+   }
+ }
+
  #[test]
  fn path_entry_pb_invalid_string(s in ".*") {
    prop_assert!(s.parse::<PathEntry>().is_err());
@@ -0,0 +1,153 @@
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+use std::convert::TryFrom;
+
+prop_compose! {
+  /// Generate `Point` values anywhere in the plane
+  fn arb_point()
+              (x in any::<i32>(), y in any::<i32>())
+              -> Point {
+    Point::new(x, y)
+  }
+}
+
+#[test]
+fn point_simple_manhattan_distance() {
+  assert_eq!(Point::ORIGIN.manhattan_distance(), 0);
+  assert_eq!(Point::new(3, -4).manhattan_distance(), 7);
+  assert_eq!(Point::new(-3, 4).manhattan_distance(), 7);
+}
+
+#[test]
+fn point_simple_no_overflow() {
+  let corner = Point::new(i32::MIN, i32::MIN);
+  assert_eq!(corner.manhattan_distance(), 1 << 32);
+  assert_eq!(
+    corner.steps_to(&Point::new(i32::MAX, i32::MAX)),
+    2 * (i64::from(i32::MAX) - i64::from(i32::MIN))
+  );
+}
+
+#[test]
+fn point_simple_walk_diagonals() {
+  let p = Point::new(1, 1);
+  let walk = |direction| p.walk(&PathEntry::new(direction, 3));
+  assert_eq!(walk(MovementDirection::UL), Point::new(-2, -2));
+  assert_eq!(walk(MovementDirection::UR), Point::new(4, -2));
+  assert_eq!(walk(MovementDirection::DL), Point::new(-2, 4));
+  assert_eq!(walk(MovementDirection::DR), Point::new(4, 4));
+}
+
+#[test]
+fn point_simple_try_walk_overflow() {
+  let entry = |direction, distance| PathEntry::new(direction, distance);
+  let edge = Point::new(i32::MAX, i32::MIN);
+  assert_eq!(edge.try_walk(&entry(MovementDirection::R, 1)), None);
+  assert_eq!(edge.try_walk(&entry(MovementDirection::U, 1)), None);
+  assert_eq!(edge.try_walk(&entry(MovementDirection::DR, 1)), None);
+  assert_eq!(
+    edge.try_walk(&entry(MovementDirection::DL, 1)),
+    Some(Point::new(i32::MAX - 1, i32::MIN + 1))
+  );
+}
+
+#[test]
+#[should_panic(expected = "does not fit in an i32")]
+fn point_simple_walk_overflow_panics() {
+  let _ =
+    Point::new(i32::MAX, 0).walk(&PathEntry::new(MovementDirection::R, 1));
+}
+
+#[test]
+fn point_simple_steps_along() {
+  assert_eq!(Point::ORIGIN.steps_along(&Point::new(3, -3)), 3);
+  assert_eq!(Point::ORIGIN.steps_along(&Point::new(0, -7)), 7);
+  assert_eq!(Point::ORIGIN.steps_to(&Point::new(3, -3)), 6);
+  assert_eq!(
+    Point::new(i32::MIN, 0).steps_along(&Point::new(i32::MAX, 0)),
+    i64::from(i32::MAX) - i64::from(i32::MIN)
+  );
+}
+
+#[test]
+fn point_simple_closer_to_zero() {
+  let (p1, p2) = (Point::new(-2, 0), Point::new(0, 3));
+  assert_eq!(p1.closer_to_zero(&p2), &p1);
+  assert_eq!(p2.closer_to_zero(&p1), &p1);
+  let p3 = Point::new(0, -2);
+  assert_eq!(p1.closer_to_zero(&p3), &p1, "Ties prefer self");
+}
+
+#[test]
+fn frame_simple_custom_origin() {
+  let frame = Frame::new(Point::new(10, 10));
+  assert_eq!(frame.distance(&Point::ORIGIN), 20);
+  let (p1, p2) = (Point::new(-2, 0), Point::new(9, 12));
+  assert_eq!(frame.closer(&p1, &p2), &p2);
+  assert_eq!(Frame::DEFAULT.closer(&p1, &p2), &p1);
+  assert_eq!(Frame::default(), Frame::DEFAULT);
+}
+
+#[test]
+fn frame_simple_closest_crossing() {
+  let wire1 = Wire::try_from(&Path::new("R8,U5,L5,D3")).unwrap();
+  let wire2 = Wire::try_from(&Path::new("U7,R6,D4,L4")).unwrap();
+  assert_eq!(closest_crossing(&wire1, &wire2), Some(Point::new(3, -3)));
+  assert_eq!(
+    Frame::new(Point::new(7, -6)).closest_crossing(&wire1, &wire2),
+    Some(Point::new(6, -5))
+  );
+}
+
+/// The distance along an overlap is smallest where it is closest to the
+/// origin of the frame, not at either end.
+#[test]
+fn frame_simple_closest_crossing_on_overlap() {
+  let wire1 = Wire::try_from(&Path::new("U2,R10")).unwrap();
+  let wire2 = Wire::try_from(&Path::new("U2,R3,L1,R20")).unwrap();
+  let frame = Frame::new(Point::new(6, 4));
+  assert_eq!(frame.closest_crossing(&wire1, &wire2), Some(Point::new(6, -2)));
+}
+
+#[test]
+fn wire_section_simple_create_across_origin() {
+  for (p1, p2) in &[
+    (Point::new(-5, 0), Point::new(3, 0)),
+    (Point::new(3, 0), Point::new(-5, 0)),
+  ] {
+    let ws = WireSection::create(*p1, *p2);
+    assert_eq!(ws.get_start(), Point::new(-5, 0));
+    assert_eq!(ws.get_end(), Point::new(3, 0));
+  }
+}
+
+proptest! {
+  #[test]
+  fn point_pb_distance_symmetric(p1 in arb_point(), p2 in arb_point()) {
+    prop_assert!(p1.steps_to(&p2) >= 0);
+    prop_assert_eq!(p1.steps_to(&p2), p2.steps_to(&p1));
+    prop_assert_eq!(Frame::new(p1).distance(&p2), p1.steps_to(&p2));
+  }
+
+  #[test]
+  fn point_pb_closer_to_zero(p1 in arb_point(), p2 in arb_point()) {
+    let closer = p1.closer_to_zero(&p2);
+    prop_assert!(closer.manhattan_distance() <= p1.manhattan_distance());
+    prop_assert!(closer.manhattan_distance() <= p2.manhattan_distance());
+  }
+
+  #[test]
+  fn wire_section_pb_create_order(p in arb_point(), other in any::<i32>(),
+                                  vertical in any::<bool>()) {
+    let q = if vertical {
+      Point::new(p.get_x(), other)
+    } else {
+      Point::new(other, p.get_y())
+    };
+    prop_assume!(p != q);
+    let ws = WireSection::create(p, q);
+    prop_assert_eq!(&ws, &WireSection::create(q, p));
+    prop_assert!(ws.get_start().get_x() <= ws.get_end().get_x());
+    prop_assert!(ws.get_start().get_y() <= ws.get_end().get_y());
+  }
+}
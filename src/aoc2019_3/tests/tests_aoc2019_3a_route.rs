@@ -0,0 +1,139 @@
+use aoc2019_3::board::Board;
+use aoc2019_3::problem_3a::*;
+use aoc2019_3::route::route;
+use proptest::prelude::*;
+use std::collections::{BTreeSet, VecDeque};
+use std::convert::TryFrom;
+
+mod common;
+use common::*;
+
+fn on_a_wire(wires: &[Wire], point: Point) -> bool {
+  wires.iter().any(|wire| wire.steps_to(&point).is_some())
+}
+
+/// Fewest steps from `start` to `goal` that stay off the wires, searching
+/// every point of the smallest rectangle around them with a margin of one,
+/// which is as far as a shortest route ever needs to go.
+fn brute_force(wires: &[Wire], start: Point, goal: Point) -> Option<i64> {
+  let points = wires.iter().flat_map(|w| w.get_corners().iter().copied());
+  let (mut min_x, mut min_y, mut max_x, mut max_y) =
+    (start.get_x(), start.get_y(), start.get_x(), start.get_y());
+  for p in points.chain(vec![goal]) {
+    min_x = min_x.min(p.get_x());
+    min_y = min_y.min(p.get_y());
+    max_x = max_x.max(p.get_x());
+    max_y = max_y.max(p.get_y());
+  }
+  if on_a_wire(wires, start) {
+    return None;
+  }
+  let mut seen = BTreeSet::new();
+  let mut queue = VecDeque::new();
+  seen.insert(start);
+  queue.push_back((start, 0));
+  while let Some((p, steps)) = queue.pop_front() {
+    if p == goal {
+      return Some(steps);
+    }
+    let (x, y) = (p.get_x(), p.get_y());
+    for &(nx, ny) in &[(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
+      let next = Point::new(nx, ny);
+      if nx >= min_x - 1
+        && nx <= max_x + 1
+        && ny >= min_y - 1
+        && ny <= max_y + 1
+        && !on_a_wire(wires, next)
+        && seen.insert(next)
+      {
+        queue.push_back((next, steps + 1));
+      }
+    }
+  }
+  None
+}
+
+fn length(p: &Path) -> i64 {
+  p.moves.iter().map(|e| i64::from(e.get_distance())).sum()
+}
+
+#[test]
+fn route_simple_no_wires() {
+  let path = route(&[], Point::new(1, 1), Point::new(4, -2)).unwrap();
+  assert_eq!(length(&path), 6);
+  assert_eq!(walk(Point::new(1, 1), &path).last(), Some(&Point::new(4, -2)));
+  assert_eq!(
+    route(&[], Point::new(1, 1), Point::new(1, 1)).unwrap().moves,
+    vec![]
+  );
+}
+
+#[test]
+fn route_simple_around_a_wall() {
+  let wires = [Wire::try_from(&Path::new("U5,D10")).unwrap()];
+  let path = route(&wires, Point::new(-1, 0), Point::new(1, 0)).unwrap();
+  assert_eq!(length(&path), 14);
+  for point in walk(Point::new(-1, 0), &path) {
+    assert!(!on_a_wire(&wires, point));
+  }
+}
+
+#[test]
+fn route_simple_through_a_diagonal() {
+  // The diagonal leaves no gap to pass between its points.
+  let wires = [Wire::try_from(&Path::new("UR3,DR3,DL3,UL3")).unwrap()];
+  assert_eq!(route(&wires, Point::new(3, 0), Point::new(3, -5)), None);
+  let path = route(&wires, Point::new(3, -4), Point::new(3, 4)).unwrap();
+  assert_eq!(length(&path), 16);
+}
+
+#[test]
+fn route_simple_blocked() {
+  let wires = [Wire::try_from(&Path::new("R2,U2,L2,D2")).unwrap()];
+  assert_eq!(route(&wires, Point::new(1, -1), Point::new(5, 5)), None);
+  assert_eq!(route(&wires, Point::new(5, 5), Point::new(1, -1)), None);
+  assert_eq!(route(&wires, Point::ORIGIN, Point::new(5, 5)), None);
+  assert_eq!(route(&wires, Point::new(2, -1), Point::new(5, 5)), None);
+}
+
+#[test]
+fn route_simple_example() {
+  let input = "R8,U5,L5,D3\nU7,R6,D4,L4";
+  let (start, goal) = (Point::new(1, -4), Point::new(7, -4));
+  let path = input.parse::<Board>().unwrap().route(start, goal).unwrap();
+  // Below the end of the first wire, and back up beside the second.
+  assert_eq!(length(&path), 12);
+  assert_eq!(path.to_string().parse::<Path>().unwrap(), path);
+
+  let mut wires: Vec<Wire> = input
+    .lines()
+    .map(|line| Wire::try_from(&Path::new(line)).unwrap())
+    .collect();
+  wires.push(Wire::starting_at(start, &path));
+  let routed = Board::new(wires);
+  assert!(routed.crossings().values().all(|wires| !wires.contains(&2)));
+  assert_eq!(routed.get_wires()[2].get_corners().last(), Some(&goal));
+}
+
+proptest! {
+  #[test]
+  fn route_pb_shortest(p1 in arb_diagonal_path(6, 10),
+                       p2 in arb_diagonal_path(6, 10),
+                       (x1, y1) in (-12..12, -12..12),
+                       (x2, y2) in (-12..12, -12..12)) {
+    let wires = [Wire::try_from(&p1).unwrap(), Wire::try_from(&p2).unwrap()];
+    let (start, goal) = (Point::new(x1, y1), Point::new(x2, y2));
+    let found = route(&wires, start, goal);
+    prop_assert_eq!(found.as_ref().map(length),
+                    brute_force(&wires, start, goal));
+    if let Some(path) = found {
+      let points = walk(start, &path);
+      prop_assert_eq!(points.last(), Some(&goal));
+      for point in points {
+        prop_assert!(!on_a_wire(&wires, point));
+      }
+      prop_assert_eq!(&path.to_string().parse::<Path>().unwrap(), &path);
+      prop_assert_eq!(&path.normalise(), &path);
+    }
+  }
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "serde")]
+
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+use serde_json::json;
+use std::convert::TryFrom;
+
+mod common;
+use common::*;
+
+#[test]
+fn serde_simple_path() {
+  let path = Path::new("R8,UL5");
+  let value = serde_json::to_value(&path).unwrap();
+  assert_eq!(
+    value,
+    json!({"moves": [
+      {"direction": "R", "distance": 8},
+      {"direction": "UL", "distance": 5},
+    ]})
+  );
+  assert_eq!(serde_json::from_value::<Path>(value).unwrap(), path);
+}
+
+/// Entries are checked as the parser checks them.
+#[test]
+fn serde_simple_bad_distance() {
+  let error = |value| serde_json::from_value::<Path>(value).unwrap_err();
+  let zero = json!({"moves": [
+    {"direction": "R", "distance": 8},
+    {"direction": "U", "distance": 0},
+  ]});
+  assert_eq!(
+    error(zero).to_string(),
+    "Zero distance not allowed in entry 1 at bytes 4..5"
+  );
+  let negative = json!({"moves": [{"direction": "L", "distance": -3}]});
+  assert_eq!(
+    error(negative).to_string(),
+    "Not a distance: -3 in entry 0 at bytes 1..3"
+  );
+
+  let entry = json!({"direction": "DR", "distance": 0});
+  assert!(serde_json::from_value::<PathEntry>(entry).is_err());
+  let entry = json!({"direction": "DR", "distance": 2});
+  assert_eq!(
+    serde_json::from_value::<PathEntry>(entry).unwrap(),
+    PathEntry::new(MovementDirection::DR, 2)
+  );
+}
+
+#[test]
+fn serde_simple_point() {
+  let value = serde_json::to_value(Point::new(3, -3)).unwrap();
+  assert_eq!(value, json!({"x": 3, "y": -3}));
+  assert_eq!(
+    serde_json::from_value::<Point>(value).unwrap(),
+    Point::new(3, -3)
+  );
+}
+
+#[test]
+fn serde_simple_wire_section() {
+  let section = WireSection::create(Point::new(8, 0), Point::new(0, 0));
+  let value = serde_json::to_value(&section).unwrap();
+  assert_eq!(value, json!([{"x": 0, "y": 0}, {"x": 8, "y": 0}]));
+  assert_eq!(serde_json::from_value::<WireSection>(value).unwrap(), section);
+
+  let bad = json!([{"x": 0, "y": 0}, {"x": 8, "y": 1}]);
+  let error = serde_json::from_value::<WireSection>(bad).unwrap_err();
+  assert_eq!(
+    error.to_string(),
+    "A WireSection must be horizontal, vertical or diagonal"
+  );
+}
+
+#[test]
+fn serde_simple_wire() {
+  let wire = Wire::try_from(&Path::new("R8,U5")).unwrap();
+  let value = serde_json::to_value(&wire).unwrap();
+  assert_eq!(
+    value,
+    json!([{"x": 0, "y": 0}, {"x": 8, "y": 0}, {"x": 8, "y": -5}])
+  );
+  assert_eq!(serde_json::from_value::<Wire>(value).unwrap(), wire);
+
+  let bad = json!([{"x": 0, "y": 0}, {"x": 0, "y": 0}]);
+  let error = serde_json::from_value::<Wire>(bad).unwrap_err();
+  assert_eq!(
+    error.to_string(),
+    "A WireSection cannot be created with 2 identical Points"
+  );
+}
+
+proptest! {
+  #[test]
+  fn serde_pb_round_trip(p in arb_diagonal_path(10, 10_000)) {
+    let json = serde_json::to_string(&p).unwrap();
+    prop_assert_eq!(&serde_json::from_str::<Path>(&json).unwrap(), &p);
+
+    let wire = Wire::try_from(&p).unwrap();
+    let json = serde_json::to_string(&wire).unwrap();
+    prop_assert_eq!(serde_json::from_str::<Wire>(&json).unwrap(), wire);
+    for section in Wire::try_from(&p).unwrap().get_sections() {
+      let json = serde_json::to_string(section).unwrap();
+      prop_assert_eq!(&serde_json::from_str::<WireSection>(&json).unwrap(),
+                      section);
+    }
+  }
+}
@@ -0,0 +1,190 @@
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+mod common;
+use common::*;
+
+/// R8,U5,L5,D3 and U7,R6,D4,L4
+#[test]
+fn solver_simple_problem1() {
+  assert_eq!(problem_3a("R8,U5,L5,D3\nU7,R6,D4,L4"), Ok(6));
+}
+
+/// R75,D30,R83,U83,L12,D49,R71,U7,L72 and U62,R66,U55,R34,D71,R55,D58,R83
+#[test]
+fn solver_simple_problem2() {
+  assert_eq!(
+    problem_3a(
+      "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83\n"
+    ),
+    Ok(159)
+  );
+}
+
+/// R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51 and
+/// U98,R91,D20,R16,D67,R40,U7,R15,U6,R7
+#[test]
+fn solver_simple_problem3() {
+  assert_eq!(
+    problem_3a(
+      "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,\
+       U7,R15,U6,R7"
+    ),
+    Ok(135)
+  );
+}
+
+#[test]
+fn solver_simple_problem1_steps() {
+  assert_eq!(problem_3b("R8,U5,L5,D3\nU7,R6,D4,L4"), Ok(30));
+}
+
+#[test]
+fn solver_simple_problem2_steps() {
+  assert_eq!(
+    problem_3b(
+      "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83"
+    ),
+    Ok(610)
+  );
+}
+
+#[test]
+fn solver_simple_problem3_steps() {
+  assert_eq!(
+    problem_3b(
+      "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,\
+       U7,R15,U6,R7"
+    ),
+    Ok(410)
+  );
+}
+
+#[test]
+fn solver_simple_fewest_steps_crossing() {
+  let wire1 = Wire::try_from(&Path::new("R8,U5,L5,D3")).unwrap();
+  let wire2 = Wire::try_from(&Path::new("U7,R6,D4,L4")).unwrap();
+  assert_eq!(
+    fewest_steps_crossing(&wire1, &wire2),
+    Some((Point::new(6, -5), 30))
+  );
+}
+
+/// The second wire loops back over its own crossing, which still counts from
+/// its first visit.
+#[test]
+fn solver_simple_steps_first_visit() {
+  assert_eq!(problem_3b("U5,R5\nR2,U10,D12,U2"), Ok(14));
+}
+
+#[test]
+fn solver_simple_mode() {
+  assert_eq!("distance".parse(), Ok(SolverMode::ClosestDistance));
+  assert_eq!("steps".parse(), Ok(SolverMode::FewestSteps));
+  assert_eq!(
+    "time".parse::<SolverMode>(),
+    Err("Not one of distance, steps: time".into())
+  );
+  let input = "R8,U5,L5,D3\nU7,R6,D4,L4";
+  assert_eq!(solve(input, SolverMode::ClosestDistance), problem_3a(input));
+  assert_eq!(solve(input, SolverMode::FewestSteps), problem_3b(input));
+}
+
+#[test]
+fn solver_simple_closest_crossing() {
+  let wire1 = Wire::try_from(&Path::new("R8,U5,L5,D3")).unwrap();
+  let wire2 = Wire::try_from(&Path::new("U7,R6,D4,L4")).unwrap();
+  assert_eq!(closest_crossing(&wire1, &wire2), Some(Point::new(3, -3)));
+}
+
+/// Wires that run along each other from the central port cross one step away.
+#[test]
+fn solver_simple_overlap_from_port() {
+  assert_eq!(problem_3a("R10\nR5,U2"), Ok(1));
+  assert_eq!(problem_3a("L3,R10\nR5,U2"), Ok(1));
+}
+
+#[test]
+fn solver_simple_no_crossing() {
+  assert_eq!(problem_3a("R5\nL5"), Err("The wires do not cross".into()));
+}
+
+#[test]
+fn solver_simple_overflow() {
+  let error = "Line 1: Entry 1 (R1) from (2147483647, 0) leaves the i32 grid";
+  assert_eq!(problem_3a("R2147483647,R1\nU1"), Err(error.into()));
+  assert_eq!(
+    Wire::try_from(&Path::new("L2147483647,L1,L1")),
+    Err("Entry 2 (L1) from (-2147483648, 0) leaves the i32 grid".into())
+  );
+  assert_eq!(
+    parse_paths("U1\n\nDR2147483647,D1").unwrap_err(),
+    format!(
+      "Line 3: Entry 1 (D1) from (2147483647, 2147483647) leaves the i32 grid"
+    )
+  );
+}
+
+#[test]
+fn solver_simple_bad_input() {
+  assert_eq!(problem_3a("R5"), Err("Expected 2 paths, found 1".into()));
+  assert!(problem_3a("R5\nX5").is_err());
+}
+
+proptest! {
+  #[test]
+  fn solver_pb_matches_brute_force(p1 in arb_path(8, 20),
+                                   p2 in arb_path(8, 20)) {
+    brute_force_3a(&p1, &p2)?;
+  }
+
+  #[test]
+  fn solver_pb_diagonal_matches_brute_force(p1 in arb_diagonal_path(8, 20),
+                                            p2 in arb_diagonal_path(8, 20)) {
+    brute_force_3a(&p1, &p2)?;
+    brute_force_3b(&p1, &p2)?;
+  }
+
+  #[test]
+  fn solver_pb_steps_match_brute_force(p1 in arb_path(8, 20),
+                                         p2 in arb_path(8, 20)) {
+    brute_force_3b(&p1, &p2)?;
+  }
+}
+
+/// Checks `problem_3a` against the closest of the points both wires visit.
+fn brute_force_3a(p1: &Path, p2: &Path) -> Result<(), TestCaseError> {
+  let points1: HashSet<Point> = walk(Point::ORIGIN, p1).into_iter().collect();
+  let expected = walk(Point::ORIGIN, p2)
+    .into_iter()
+    .filter(|p| *p != Point::ORIGIN && points1.contains(p))
+    .map(|p| Point::ORIGIN.steps_to(&p))
+    .min();
+
+  let input = format!("{p1}\n{p2}");
+  prop_assert_eq!(problem_3a(&input).ok(), expected);
+  Ok(())
+}
+
+/// Checks `problem_3b` against the first visits of the points both wires
+/// visit.
+fn brute_force_3b(p1: &Path, p2: &Path) -> Result<(), TestCaseError> {
+  let mut steps1 = HashMap::new();
+  for (steps, p) in walk(Point::ORIGIN, p1).into_iter().enumerate() {
+    steps1.entry(p).or_insert(steps);
+  }
+  let mut seen2 = HashSet::new();
+  let expected = walk(Point::ORIGIN, p2)
+    .into_iter()
+    .enumerate()
+    .filter(|&(_, p)| p != Point::ORIGIN && seen2.insert(p))
+    .filter_map(|(steps, p)| Some(steps + steps1.get(&p)?))
+    .min()
+    .map(|steps| steps as i64);
+
+  let input = format!("{p1}\n{p2}");
+  prop_assert_eq!(problem_3b(&input).ok(), expected);
+  Ok(())
+}
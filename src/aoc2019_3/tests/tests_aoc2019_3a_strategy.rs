@@ -1,39 +1,52 @@
-#![feature(test)]
-
-use aoc2019_3::problem_3a::MovementDirection::{D, L, R, U};
 use aoc2019_3::problem_3a::*;
 use proptest::prelude::*;
 
-/// Generate `MovementDirection` values
-fn strategy_movement_direction() -> impl Strategy<Value = MovementDirection> {
-  prop_oneof![
-    Just(MovementDirection::U),
-    Just(MovementDirection::D),
-    Just(MovementDirection::L),
-    Just(MovementDirection::R),
-  ]
-}
+mod common;
+use common::*;
 
 /// Generate `Orientation` values
 fn strategy_orientation() -> impl Strategy<Value = Orientation> {
-  prop_oneof![Just(Orientation::Horizontal), Just(Orientation::Vertical),]
+  prop_oneof![
+    Just(Orientation::Horizontal),
+    Just(Orientation::Vertical),
+    Just(Orientation::Diagonal),
+    Just(Orientation::AntiDiagonal),
+  ]
 }
 
-prop_compose! {
-  /// Generate `PathEntry` values
-  fn arb_pathentry()
-                  (direction in strategy_movement_direction(),
-                   distance in 1..10_000)
-                  -> PathEntry {
-    PathEntry::new(direction, distance)
+proptest! {
+  #[test]
+  fn strategy_pb_pathentry_parses(e in arb_pathentry(10_000)) {
+    let s = format!("{}{}", e.get_direction(), e.get_distance());
+    prop_assert_eq!(s.parse::<PathEntry>().unwrap(), e);
+  }
+
+  #[test]
+  fn strategy_pb_path_length(p in arb_path(10, 10_000)) {
+    prop_assert!(!p.moves.is_empty() && p.moves.len() < 10);
+  }
+
+  #[test]
+  fn strategy_pb_eight_way_parses(d in strategy_eight_way_direction()) {
+    prop_assert_eq!(d.to_string().parse::<MovementDirection>().unwrap(), d);
+  }
+
+  #[test]
+  fn strategy_pb_diagonal_path(p in arb_diagonal_path(10, 20)) {
+    prop_assert!(!p.moves.is_empty() && p.moves.len() < 10);
+    prop_assert!(p.moves.iter().all(|e| (1..20).contains(&e.get_distance())));
+    prop_assert_eq!(p.to_string().parse::<Path>().unwrap(), p);
   }
-}
 
-prop_compose! {
-  /// Generate `Path` values
-  fn arb_path(max_segments: usize)
-             (vec in prop::collection::vec(arb_pathentry(), 1..max_segments))
-             -> Path {
-    Path::new_internal(vec)
+  #[test]
+  fn strategy_pb_orientation(o in strategy_orientation(), d in 1..10_000) {
+    let end = match o {
+      Orientation::Horizontal => Point::new(d, 0),
+      Orientation::Vertical => Point::new(0, d),
+      Orientation::Diagonal => Point::new(d, d),
+      Orientation::AntiDiagonal => Point::new(d, -d),
+    };
+    let ws = WireSection::create(Point::new(0, 0), end);
+    prop_assert_eq!(ws.get_orientation(), o);
   }
 }
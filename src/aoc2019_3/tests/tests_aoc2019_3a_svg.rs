@@ -0,0 +1,85 @@
+use aoc2019_3::board::Board;
+use aoc2019_3::problem_3a::*;
+use aoc2019_3::svg::*;
+
+fn board(input: &str) -> Board { input.parse().unwrap() }
+
+#[test]
+fn svg_simple_colours() {
+  assert_ne!(colour(0), colour(1));
+  assert_eq!(colour(PALETTE.len()), colour(0));
+}
+
+#[test]
+fn svg_simple_example() {
+  let svg = render(&board("R8,U5,L5,D3\nU7,R6,D4,L4"), Frame::DEFAULT);
+  assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+  assert!(svg.ends_with("</svg>\n"));
+  // Small drawings keep a minimum unit of 0.1, so the margin is 0.4.
+  assert!(svg.contains("viewBox=\"-0.4 -7.4 8.8 7.8\""));
+  assert!(svg.contains(&format!(
+    "<polyline stroke=\"{}\" points=\"0,0 8,0 8,-5 3,-5 3,-2\">",
+    colour(0)
+  )));
+  assert!(svg.contains(&format!(
+    "<polyline stroke=\"{}\" points=\"0,0 0,-7 6,-7 6,-3 2,-3\">",
+    colour(1)
+  )));
+  assert_eq!(svg.matches("fill=\"black\"><title>").count(), 2);
+  assert!(svg.contains("<title>Closest crossing (3, -3), distance 6</title>"));
+  assert!(svg.contains(">origin</text>"));
+}
+
+#[test]
+fn svg_simple_scales_with_the_drawing() {
+  let svg = render(&board("R500,U200\nU300"), Frame::DEFAULT);
+  // The drawing is 500 wide, so the unit is 5 and the margin 20.
+  assert!(svg.contains("viewBox=\"-20 -320 540 340\""));
+  assert!(svg.contains("stroke-width=\"5\""));
+}
+
+#[test]
+fn svg_simple_frame() {
+  let svg =
+    render(&board("R8,U5,L5,D3\nU7,R6,D4,L4"), Frame::new(Point::new(8, -8)));
+  assert!(svg.contains("<title>Closest crossing (6, -5), distance 5</title>"));
+}
+
+/// An overlap is one segment rather than a dot at each of its points.
+#[test]
+fn svg_simple_overlap() {
+  let svg = render(&board("R8\nU1,R2,D1,R3,U1\nD1,R1,U2"), Frame::DEFAULT);
+  assert_eq!(svg.matches("<line").count(), 1);
+  assert!(
+    svg.contains("<line x1=\"2\" y1=\"0\" x2=\"5\" y2=\"0\" stroke=\"black\"")
+  );
+  assert!(
+    svg.contains("<title>Overlap (2, 0) to (5, 0): wires [0, 1]</title>")
+  );
+  // The third wire crosses the first, and ends on the second.
+  assert_eq!(svg.matches("fill=\"black\"><title>").count(), 2);
+  assert!(svg.contains("<title>(1, 0): wires [0, 2]</title>"));
+  assert!(svg.contains("<title>(1, -1): wires [1, 2]</title>"));
+  assert!(svg.contains("<title>Closest crossing (1, 0), distance 1</title>"));
+}
+
+/// A long overlap is drawn as one line, and its closest point ringed.
+#[test]
+fn svg_simple_long_overlap() {
+  let svg = render(&board("R2000000000\nR2000000000"), Frame::DEFAULT);
+  assert_eq!(svg.matches("<line").count(), 1);
+  assert_eq!(svg.matches("fill=\"black\"><title>").count(), 0);
+  assert!(svg.contains("<title>Closest crossing (1, 0), distance 1</title>"));
+  let frame = Frame::new(Point::new(7, 3));
+  let svg = render(&board("R2000000000\nR2000000000"), frame);
+  assert!(svg.contains("<title>Closest crossing (7, 0), distance 3</title>"));
+}
+
+#[test]
+fn svg_simple_no_crossings() {
+  let svg = render(&board("R8\nL8"), Frame::DEFAULT);
+  assert!(!svg.contains("<circle"));
+  assert!(!svg.contains("<line"));
+  assert_eq!(svg.matches("<polyline").count(), 2);
+  assert!(svg.contains(">origin</text>"));
+}
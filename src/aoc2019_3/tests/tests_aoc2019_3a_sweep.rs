@@ -0,0 +1,90 @@
+use aoc2019_3::problem_3a::*;
+use aoc2019_3::sweep;
+use proptest::prelude::*;
+use std::convert::TryFrom;
+
+mod common;
+use common::*;
+
+/// Both lists of intersections, sorted so they can be compared.
+fn both(path1: &str, path2: &str) -> (Vec<Intersection>, Vec<Intersection>) {
+  let (wire1, wire2) = (
+    Wire::try_from(&Path::new(path1)).unwrap(),
+    Wire::try_from(&Path::new(path2)).unwrap(),
+  );
+  let mut pairwise = wire1.crossings(&wire2);
+  let mut swept = sweep::crossings(&wire1, &wire2);
+  pairwise.sort();
+  swept.sort();
+  (pairwise, swept)
+}
+
+#[test]
+fn sweep_simple_diagonals() {
+  let (pairwise, swept) = both("UR4,R2,DL3", "R3,U6,DL1,DR1");
+  assert_eq!(swept, pairwise);
+  assert!(swept.contains(&Intersection::Point(Point::new(3, -3))));
+  assert!(swept.contains(&Intersection::Point(Point::new(3, -1))));
+}
+
+#[test]
+fn sweep_simple_problem1() {
+  let (pairwise, swept) = both("R8,U5,L5,D3", "U7,R6,D4,L4");
+  assert_eq!(swept, vec![
+    Intersection::Point(Point::new(0, 0)),
+    Intersection::Point(Point::new(3, -3)),
+    Intersection::Point(Point::new(6, -5)),
+  ]);
+  assert_eq!(swept, pairwise);
+}
+
+#[test]
+fn sweep_simple_touching_ends() {
+  let (pairwise, swept) = both("R5,U5", "U5,R5,D5");
+  assert_eq!(swept, pairwise);
+  assert!(swept.contains(&Intersection::Point(Point::new(5, -5))));
+}
+
+#[test]
+fn sweep_simple_overlaps() {
+  let (pairwise, swept) = both("R10,U3,L10", "R4,L8,U3,R20");
+  assert_eq!(swept, pairwise);
+  assert!(swept.contains(&Intersection::Overlap(Overlap::new(
+    Point::new(0, -3),
+    Point::new(10, -3)
+  ))));
+}
+
+#[test]
+fn sweep_simple_same_wire_ignored() {
+  let wire1 = Wire::try_from(&Path::new("R5,U2,L2,D5")).unwrap();
+  let wire2 = Wire::try_from(&Path::new("L1")).unwrap();
+  let mut swept = sweep::crossings(&wire1, &wire2);
+  swept.sort();
+  assert_eq!(swept, vec![Intersection::Point(Point::new(0, 0))]);
+}
+
+proptest! {
+  #[test]
+  fn sweep_pb_matches_pairwise(p1 in arb_path(30, 10), p2 in arb_path(30, 10)) {
+    let wire1 = Wire::try_from(&p1).unwrap();
+    let wire2 = Wire::try_from(&p2).unwrap();
+    let mut pairwise = wire1.crossings(&wire2);
+    let mut swept = sweep::crossings(&wire1, &wire2);
+    pairwise.sort();
+    swept.sort();
+    prop_assert_eq!(swept, pairwise);
+  }
+
+  #[test]
+  fn sweep_pb_diagonal_matches_pairwise(p1 in arb_diagonal_path(30, 10),
+                                        p2 in arb_diagonal_path(30, 10)) {
+    let wire1 = Wire::try_from(&p1).unwrap();
+    let wire2 = Wire::try_from(&p2).unwrap();
+    let mut pairwise = wire1.crossings(&wire2);
+    let mut swept = sweep::crossings(&wire1, &wire2);
+    pairwise.sort();
+    swept.sort();
+    prop_assert_eq!(swept, pairwise);
+  }
+}
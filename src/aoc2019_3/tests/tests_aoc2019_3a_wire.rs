@@ -0,0 +1,182 @@
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+use std::convert::TryFrom;
+
+mod common;
+use common::*;
+
+/// R8,U5,L5,D3
+#[test]
+fn wire_simple_problem1() {
+  let wire = Wire::try_from(&Path::new("R8,U5,L5,D3")).unwrap();
+  assert_eq!(wire.get_corners(), &[
+    Point::new(0, 0),
+    Point::new(8, 0),
+    Point::new(8, -5),
+    Point::new(3, -5),
+    Point::new(3, -2),
+  ]);
+  assert_eq!(wire.get_step_counts(), &[0, 8, 13, 18]);
+  assert_eq!(
+    wire.get_sections()[1],
+    WireSection::create(Point::new(8, -5), Point::new(8, 0))
+  );
+  assert_eq!(wire.length(), 21);
+}
+
+/// Both crossings of the first example are reached in 20 steps by this wire.
+#[test]
+fn wire_simple_steps_to() {
+  let wire = Wire::try_from(&Path::new("R8,U5,L5,D3")).unwrap();
+  assert_eq!(wire.steps_to(&Point::new(3, -3)), Some(20));
+  assert_eq!(wire.steps_to(&Point::new(6, -5)), Some(15));
+  assert_eq!(wire.steps_to(&Point::new(0, 0)), Some(0));
+  assert_eq!(wire.steps_to(&Point::new(3, 0)), Some(3));
+  assert_eq!(wire.steps_to(&Point::new(1, 1)), None);
+}
+
+/// A wire that passes through a point twice reports the first visit.
+#[test]
+fn wire_simple_steps_to_revisited() {
+  let wire = Wire::try_from(&Path::new("R4,U2,L2,D4")).unwrap();
+  assert_eq!(wire.steps_to(&Point::new(2, 0)), Some(2));
+}
+
+#[test]
+fn wire_simple_new() {
+  let wire =
+    Wire::new(vec![Point::new(0, 0), Point::new(8, 0), Point::new(8, 5)]);
+  assert_eq!(wire.get_sections(), &[
+    WireSection::create(Point::new(0, 0), Point::new(8, 0)),
+    WireSection::create(Point::new(8, 0), Point::new(8, 5)),
+  ]);
+  assert_eq!(wire.get_step_counts(), &[0, 8]);
+}
+
+#[test]
+#[should_panic(
+  expected = "A WireSection must be horizontal, vertical or diagonal"
+)]
+fn wire_simple_new_not_straight() {
+  let _ = Wire::new(vec![Point::new(0, 0), Point::new(1, 2)]);
+}
+
+/// A diagonal step moves one cell, so it counts as one step.
+#[test]
+fn wire_simple_diagonal_steps() {
+  let wire = Wire::try_from(&Path::new("UR3,D3,L1,UL2")).unwrap();
+  assert_eq!(wire.get_step_counts(), &[0, 3, 6, 7]);
+  assert_eq!(wire.length(), 9);
+  assert_eq!(wire.steps_to(&Point::new(2, -2)), Some(2));
+  assert_eq!(wire.steps_to(&Point::new(0, -2)), Some(9));
+}
+
+#[test]
+fn wire_simple_metrics() {
+  let wire = Wire::try_from(&Path::new("R8,U5,L5,D3")).unwrap();
+  assert_eq!(wire.bounding_box(), Some((0, -5, 8, 0)));
+  assert_eq!(wire.turns(), 3);
+  assert_eq!(wire.longest_run(), Some((Point::ORIGIN, Point::new(8, 0))));
+  assert_eq!(wire.farthest_point(), Some(Point::new(8, -5)));
+  assert_eq!(
+    Frame::new(Point::new(8, -5)).farthest_point(&wire),
+    Some(Point::ORIGIN)
+  );
+}
+
+/// Moves in the same direction make one straight run, without a turn.
+#[test]
+fn wire_simple_metrics_merged_runs() {
+  let wire = Wire::try_from(&Path::new("R3,R4,U2,L7,L1")).unwrap();
+  assert_eq!(wire.turns(), 2);
+  assert_eq!(wire.longest_run(), Some((Point::new(7, -2), Point::new(-1, -2))));
+  assert_eq!(Wire::try_from(&Path::new("R5,L2")).unwrap().turns(), 1);
+  assert_eq!(
+    Wire::try_from(&Path::new("R2,U2")).unwrap().longest_run(),
+    Some((Point::ORIGIN, Point::new(2, 0)))
+  );
+  assert_eq!(
+    Wire::try_from(&Path::new("UR3,R3,D1")).unwrap().longest_run(),
+    Some((Point::ORIGIN, Point::new(3, -3)))
+  );
+}
+
+#[test]
+fn wire_simple_metrics_empty() {
+  let wire = Wire::try_from(&Path::new("")).unwrap();
+  assert_eq!(wire.bounding_box(), Some((0, 0, 0, 0)));
+  assert_eq!(wire.turns(), 0);
+  assert_eq!(wire.longest_run(), None);
+  assert_eq!(wire.farthest_point(), Some(Point::ORIGIN));
+
+  let wire = Wire::new(vec![]);
+  assert_eq!(wire.bounding_box(), None);
+  assert_eq!(wire.farthest_point(), None);
+}
+
+proptest! {
+  #[test]
+  fn wire_pb_metrics(p in arb_path(10, 10_000)) {
+    let wire = Wire::try_from(&p).unwrap();
+    let points = walk(Point::ORIGIN, &p);
+    let xs = points.iter().map(Point::get_x);
+    let ys = points.iter().map(Point::get_y);
+    prop_assert_eq!(wire.bounding_box(), Some((
+      xs.clone().min().unwrap(),
+      ys.clone().min().unwrap(),
+      xs.max().unwrap(),
+      ys.max().unwrap(),
+    )));
+    prop_assert_eq!(
+      wire.farthest_point().map(|p| p.manhattan_distance()),
+      points.iter().map(Point::manhattan_distance).max()
+    );
+
+    // The direction of every step, and how many steps in a row go the same
+    // way.
+    let steps: Vec<(i32, i32)> = points
+      .windows(2)
+      .map(|w| (w[1].get_x() - w[0].get_x(), w[1].get_y() - w[0].get_y()))
+      .collect();
+    let turns = steps.windows(2).filter(|w| w[0] != w[1]).count();
+    prop_assert_eq!(wire.turns(), turns);
+    let mut longest = 0;
+    let mut run = 0;
+    for (i, step) in steps.iter().enumerate() {
+      run = if i > 0 && steps[i - 1] == *step { run + 1 } else { 1 };
+      longest = longest.max(run);
+    }
+    let (start, end) = wire.longest_run().unwrap();
+    prop_assert_eq!(start.steps_along(&end), longest);
+  }
+
+  #[test]
+  fn wire_pb_corners_follow_path(p in arb_path(50, 10_000)) {
+    let wire = Wire::try_from(&p).unwrap();
+    let corners = wire.get_corners();
+    prop_assert_eq!(corners.len(), p.moves.len() + 1);
+    prop_assert_eq!(corners[0], Point::ORIGIN);
+    for (pair, entry) in corners.windows(2).zip(&p.moves) {
+      prop_assert_eq!(pair[0].walk(entry), pair[1]);
+    }
+  }
+
+  #[test]
+  fn wire_pb_length(p in arb_path(50, 10_000)) {
+    let wire = Wire::try_from(&p).unwrap();
+    let distances: i64 =
+      p.moves.iter().map(|e| i64::from(e.get_distance())).sum();
+    prop_assert_eq!(wire.length(), distances);
+  }
+
+  #[test]
+  fn wire_pb_step_counts(p in arb_path(50, 10_000)) {
+    let wire = Wire::try_from(&p).unwrap();
+    let steps = wire.get_step_counts();
+    prop_assert_eq!(steps.len(), wire.get_sections().len());
+    for (i, corner) in wire.get_corners().iter().enumerate().take(steps.len()) {
+      // Each corner is reached no later than the walk first gets there.
+      prop_assert!(wire.steps_to(corner).unwrap() <= steps[i]);
+    }
+  }
+}
@@ -0,0 +1,214 @@
+use aoc2019_3::problem_3a::*;
+use proptest::prelude::*;
+
+/// Generate `Orientation` values
+fn strategy_orientation() -> impl Strategy<Value = Orientation> {
+  prop_oneof![
+    Just(Orientation::Horizontal),
+    Just(Orientation::Vertical),
+    Just(Orientation::Diagonal),
+    Just(Orientation::AntiDiagonal),
+  ]
+}
+
+prop_compose! {
+  /// Generate `WireSection` values close enough together that they often meet
+  fn arb_wiresection()
+                    (x in -50..50, y in -50..50,
+                     orientation in strategy_orientation(),
+                     length in prop_oneof![-50..0, 1..50])
+                    -> WireSection {
+    let end = match orientation {
+      Orientation::Horizontal => Point::new(x + length, y),
+      Orientation::Vertical => Point::new(x, y + length),
+      Orientation::Diagonal => Point::new(x + length, y + length),
+      Orientation::AntiDiagonal => Point::new(x + length, y - length),
+    };
+    WireSection::create(Point::new(x, y), end)
+  }
+}
+
+fn section(x1: i32, y1: i32, x2: i32, y2: i32) -> WireSection {
+  WireSection::create(Point::new(x1, y1), Point::new(x2, y2))
+}
+
+#[test]
+fn wire_section_simple_create_orders_points() {
+  let ws = section(5, 0, -3, 0);
+  assert_eq!(ws.get_start(), Point::new(-3, 0));
+  assert_eq!(ws.get_end(), Point::new(5, 0));
+  assert_eq!(ws.get_orientation(), Orientation::Horizontal);
+}
+
+#[test]
+#[should_panic(expected = "A WireSection cannot be created with 2 identical")]
+fn wire_section_simple_identical_points() { section(1, 1, 1, 1); }
+
+#[test]
+#[should_panic(
+  expected = "A WireSection must be horizontal, vertical or diagonal"
+)]
+fn wire_section_simple_not_straight() { section(0, 0, 1, 2); }
+
+#[test]
+fn wire_section_simple_diagonal() {
+  let ws = section(3, 3, 0, 0);
+  assert_eq!(ws.get_start(), Point::new(0, 0));
+  assert_eq!(ws.get_end(), Point::new(3, 3));
+  assert_eq!(ws.get_orientation(), Orientation::Diagonal);
+  assert!(ws.contains(&Point::new(2, 2)));
+  assert!(!ws.contains(&Point::new(2, 1)));
+
+  let ws = section(3, -3, 0, 0);
+  assert_eq!(ws.get_start(), Point::new(0, 0));
+  assert_eq!(ws.get_end(), Point::new(3, -3));
+  assert_eq!(ws.get_orientation(), Orientation::AntiDiagonal);
+  assert!(ws.contains(&Point::new(1, -1)));
+  assert!(!ws.contains(&Point::new(1, 1)));
+}
+
+#[test]
+fn wire_section_simple_diagonal_crossing() {
+  let d = section(0, 0, 6, 6);
+  assert_eq!(
+    d.intersection(&section(0, 4, 9, 4)),
+    Some(Intersection::Point(Point::new(4, 4)))
+  );
+  assert_eq!(
+    d.intersection(&section(2, -5, 2, 5)),
+    Some(Intersection::Point(Point::new(2, 2)))
+  );
+  assert_eq!(
+    d.intersection(&section(0, 6, 6, 0)),
+    Some(Intersection::Point(Point::new(3, 3)))
+  );
+  assert_eq!(d.intersection(&section(7, 0, 7, 9)), None);
+}
+
+/// Diagonals of opposite slopes can pass each other between grid points,
+/// where wires cannot meet.
+#[test]
+fn wire_section_simple_diagonal_between_points() {
+  assert_eq!(section(0, 0, 1, 1).intersection(&section(0, 1, 1, 0)), None);
+  assert_eq!(section(0, 0, 5, 5).intersection(&section(0, 5, 5, 0)), None);
+}
+
+#[test]
+fn wire_section_simple_diagonal_overlap() {
+  assert_eq!(
+    section(0, 0, 6, 6).intersection(&section(8, 8, 4, 4)),
+    Some(Intersection::Overlap(Overlap::new(
+      Point::new(4, 4),
+      Point::new(6, 6)
+    )))
+  );
+  assert_eq!(
+    section(0, 0, 6, -6).intersection(&section(6, -6, 9, -9)),
+    Some(Intersection::Point(Point::new(6, -6)))
+  );
+  assert_eq!(section(0, 0, 6, 6).intersection(&section(0, 1, 6, 7)), None);
+}
+
+#[test]
+fn wire_section_simple_perpendicular() {
+  let h = section(0, 5, 10, 5);
+  let v = section(3, 0, 3, 8);
+  assert_eq!(h.intersection(&v), Some(Intersection::Point(Point::new(3, 5))));
+  assert_eq!(v.intersection(&h), Some(Intersection::Point(Point::new(3, 5))));
+}
+
+#[test]
+fn wire_section_simple_perpendicular_apart() {
+  assert_eq!(section(0, 5, 10, 5).intersection(&section(3, 6, 3, 8)), None);
+  assert_eq!(section(0, 5, 10, 5).intersection(&section(11, 0, 11, 8)), None);
+}
+
+#[test]
+fn wire_section_simple_perpendicular_touching() {
+  let h = section(0, 5, 10, 5);
+  let v = section(10, 5, 10, 8);
+  assert_eq!(h.intersection(&v), Some(Intersection::Point(Point::new(10, 5))));
+}
+
+#[test]
+fn wire_section_simple_parallel() {
+  assert_eq!(section(0, 0, 10, 0).intersection(&section(0, 1, 10, 1)), None);
+  assert_eq!(section(0, 0, 0, 10).intersection(&section(1, 0, 1, 10)), None);
+}
+
+#[test]
+fn wire_section_simple_collinear_apart() {
+  assert_eq!(section(0, 0, 4, 0).intersection(&section(5, 0, 10, 0)), None);
+  assert_eq!(section(0, -9, 0, -5).intersection(&section(0, 0, 0, 3)), None);
+}
+
+#[test]
+fn wire_section_simple_collinear_overlap() {
+  assert_eq!(
+    section(0, 0, 6, 0).intersection(&section(10, 0, 4, 0)),
+    Some(Intersection::Overlap(Overlap::new(
+      Point::new(4, 0),
+      Point::new(6, 0)
+    )))
+  );
+  assert_eq!(
+    section(2, -10, 2, 10).intersection(&section(2, -3, 2, 3)),
+    Some(Intersection::Overlap(Overlap::new(
+      Point::new(2, -3),
+      Point::new(2, 3)
+    )))
+  );
+}
+
+#[test]
+fn wire_section_simple_collinear_touching() {
+  assert_eq!(
+    section(0, 0, 4, 0).intersection(&section(4, 0, 10, 0)),
+    Some(Intersection::Point(Point::new(4, 0)))
+  );
+}
+
+proptest! {
+  #[test]
+  fn wire_section_pb_symmetric(a in arb_wiresection(), b in arb_wiresection()) {
+    prop_assert_eq!(a.intersection(&b), b.intersection(&a));
+  }
+
+  #[test]
+  fn wire_section_pb_contained(a in arb_wiresection(), b in arb_wiresection()) {
+    match a.intersection(&b) {
+      None => (),
+      Some(Intersection::Point(p)) => {
+        prop_assert!(a.contains(&p) && b.contains(&p));
+      },
+      Some(Intersection::Overlap(overlap)) => {
+        let (start, end) = (overlap.get_start(), overlap.get_end());
+        prop_assert_eq!(a.get_orientation(), b.get_orientation());
+        prop_assert!(a.contains(&start) && b.contains(&start));
+        prop_assert!(a.contains(&end) && b.contains(&end));
+        prop_assert_eq!(
+          WireSection::create(start, end).get_start(),
+          start
+        );
+      },
+    }
+  }
+
+  #[test]
+  fn wire_section_pb_self(a in arb_wiresection()) {
+    prop_assert_eq!(
+      a.intersection(&a),
+      Some(Intersection::Overlap(Overlap::new(a.get_start(), a.get_end())))
+    );
+  }
+
+  #[test]
+  fn wire_section_pb_crossing(x in -50..50, y in -50..50,
+                              left in 1..50, right in 1..50,
+                              up in 1..50, down in 1..50) {
+    let h = section(x - left, y, x + right, y);
+    let v = section(x, y - up, x, y + down);
+    let p = Point::new(x, y);
+    prop_assert_eq!(h.intersection(&v), Some(Intersection::Point(p)));
+  }
+}